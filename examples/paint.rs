@@ -0,0 +1,324 @@
+// without the console backend of the platform, the demo only explains how to
+// build it and the paint code is unused.
+#![cfg_attr(
+    not(any(
+        all(unix, feature = "unix-backend"),
+        all(windows, feature = "windows-backend")
+    )),
+    allow(dead_code, unused_imports)
+)]
+
+extern crate rustlike;
+use rustlike::core::application::Application;
+use rustlike::core::drawing::blend_mode::BlendMode;
+use rustlike::core::drawing::canvas::Canvas;
+use rustlike::core::drawing::cell::Cell;
+use rustlike::core::drawing::cell_blend::CellBlend;
+use rustlike::core::drawing::cell_buffer::CellBuffer;
+use rustlike::core::drawing::cell_buffer_view_mut::CellBufferViewMut;
+use rustlike::core::drawing::color::Color;
+use rustlike::core::drawing::layer::Layer;
+use rustlike::core::drawing::point_2d::Point2d;
+use rustlike::core::drawing::rect::Rect;
+use rustlike::core::drawing::size_2d::Size2d;
+use rustlike::core::drawing::style::Style;
+use rustlike::core::events::event::{
+    Event, KeyboardEvent, KeyboardEventType, MouseEvent, MouseEventType, WindowEventType,
+};
+use rustlike::core::game_loop::frame_stats::FrameStats;
+use rustlike::core::game_loop::game::Game;
+use rustlike::core::game_loop::game_runner::GameRunner;
+use rustlike::core::input::drag::Drag;
+use rustlike::core::input::drag_phase::DragPhase;
+use rustlike::core::input::key::Key;
+use rustlike::core::input::mouse_button::MouseButton;
+use rustlike::core::Result;
+#[cfg(all(unix, feature = "unix-backend"))]
+use rustlike::unix::application::UnixApplication as PlatformApplication;
+#[cfg(all(windows, feature = "windows-backend"))]
+use rustlike::windows::application::WindowsApplication as PlatformApplication;
+use std::time::Duration;
+
+/// Paints with the mouse on a canvas, and shows the last keyboard events.
+/// Dragging with the right button selects a rectangle, highlighted by a
+/// layer that only tints the background, that is erased on release.
+struct Paint {
+    canvas: CellBuffer,
+    selection: Layer,
+    drag: Option<Drag>,
+    clicks: u32,
+    fps: u32,
+    frame_time: Duration,
+    quit: bool,
+}
+
+impl Game for Paint {
+    fn handle_event(&mut self, event: Event, application: &dyn Application) {
+        match event {
+            Event::Keyboard(keyboard) if keyboard.key == Key::Escape => self.quit = true,
+            Event::Window(window) if window.event_type == WindowEventType::WindowResize => {
+                self.canvas
+                    .resize(Cell::new(' ', Color::Black, Color::Black), window.size);
+                self.selection.resize(window.size);
+            }
+            Event::Mouse(mouse) => {
+                process_mouse_events(mouse, &mut self.canvas);
+
+                // the mouse state already holds every event of the frame.
+                let mouse_state = application.get_mouse_state();
+
+                if mouse_state.is_pressed(MouseButton::Left) {
+                    self.clicks = mouse_state.get_clicks(MouseButton::Left);
+                }
+
+                self.drag = mouse_state.get_drag();
+
+                match self.drag {
+                    Some(drag)
+                        if drag.button == MouseButton::Right && drag.phase == DragPhase::Ended =>
+                    {
+                        fill_selection(
+                            &mut self.canvas,
+                            drag,
+                            Cell::new(' ', Color::Black, Color::Black),
+                        )
+                    }
+                    _ => (),
+                }
+            }
+            Event::Keyboard(keyboard) => process_keyboard_events(keyboard, &mut self.canvas),
+            _ => (),
+        }
+    }
+
+    fn update(&mut self, _dt: Duration, _application: &dyn Application) {}
+
+    fn draw(&mut self, buffer: &mut CellBuffer) {
+        buffer.write_cell_buffer(&self.canvas, Point2d::empty());
+
+        match self.drag {
+            Some(drag) if drag.button == MouseButton::Right && drag.phase != DragPhase::Ended => {
+                self.selection.clear();
+                self.selection
+                    .fill_rect(drag.get_rect(), Cell::new(' ', Color::White, Color::Blue));
+                self.selection.compose_onto(buffer);
+            }
+            _ => (),
+        }
+
+        let console_size = buffer.size;
+        let mut stats = buffer.get_view_mut(Rect::from_size(Size2d::new(console_size.width, 2)));
+        draw_stats(
+            &mut stats,
+            console_size,
+            self.fps,
+            self.frame_time,
+            self.clicks,
+        );
+    }
+
+    fn end_frame(&mut self, stats: &FrameStats) {
+        self.fps = stats.get_fps();
+        self.frame_time = stats.get_frame_time();
+    }
+
+    fn should_quit(&self) -> bool {
+        self.quit
+    }
+}
+
+#[cfg(any(
+    all(unix, feature = "unix-backend"),
+    all(windows, feature = "windows-backend")
+))]
+fn main() -> Result<()> {
+    let mut application = PlatformApplication::create()?;
+
+    {
+        let window = application.get_window();
+        window.set_window_position(Point2d::empty())?;
+        window.set_window_size(Size2d::new(800, 600))?;
+
+        let mouse = application.get_mouse();
+        mouse.show_cursor(false)?;
+
+        let terminal = application.get_terminal();
+        terminal.clear()?;
+        terminal.set_cursor(Point2d::empty())?;
+        terminal.set_cursor_visibility(false)?;
+    }
+
+    let console_size = application.get_terminal().get_console_size()?;
+    let mut paint = Paint {
+        canvas: CellBuffer::new(Cell::new(' ', Color::Black, Color::Black), console_size),
+        selection: Layer::new(console_size, 0),
+        drag: None,
+        clicks: 0,
+        fps: 0,
+        frame_time: Duration::from_secs(0),
+        quit: false,
+    };
+
+    paint
+        .selection
+        .set_blend(CellBlend::background_only(BlendMode::Alpha(160)));
+
+    let mut runner = GameRunner::new();
+    runner.set_fps_limit(Some(60));
+    runner.run(&mut application, &mut paint)?;
+
+    application.get_terminal().dispose()
+}
+
+#[cfg(not(any(
+    all(unix, feature = "unix-backend"),
+    all(windows, feature = "windows-backend")
+)))]
+fn main() -> Result<()> {
+    eprintln!("The paint example needs the unix-backend or windows-backend feature.");
+    Ok(())
+}
+
+fn draw_stats(
+    view: &mut CellBufferViewMut,
+    console_size: Size2d,
+    fps: u32,
+    frame_time: Duration,
+    clicks: u32,
+) {
+    let text_background = Cell::new(' ', Color::White, Color::DarkGrey);
+    let separator = Cell::new('¯', Color::Grey, Color::Black);
+    let width = view.get_size().width;
+
+    view.repeat_cell(text_background, Point2d::new(0, 0), width);
+    view.repeat_cell(separator, Point2d::new(0, 1), width);
+    view.write_string(
+        &format!(
+            "FPS: {}   Frame: {:.2} ms   Console({}, {})   Clicks: {}",
+            fps,
+            frame_time.as_secs_f64() * 1000.0,
+            console_size.width,
+            console_size.height,
+            clicks
+        ),
+        Point2d::empty(),
+        Color::White,
+        text_background.background,
+        Style::BOLD,
+    );
+}
+
+fn process_mouse_events(mouse: MouseEvent, buffer: &mut CellBuffer) {
+    let pressed = mouse.event_type == MouseEventType::Click
+        || mouse.event_type == MouseEventType::DoubleClick;
+
+    if (pressed || mouse.event_type == MouseEventType::MouseMove) && mouse.left_button {
+        buffer.set(mouse.position, Cell::new('░', Color::White, Color::Black));
+    }
+
+    if pressed && mouse.right_button {
+        buffer.set(mouse.position, Cell::new(' ', Color::Black, Color::Black));
+    }
+
+    if let MouseEventType::Scroll { axis, lines } = mouse.event_type {
+        buffer.write_string(
+            &format!("{:?} scroll {:+}  ", axis, lines),
+            Point2d::new(0, 2),
+            Color::White,
+            Color::DarkBlue,
+            Style::NONE,
+        );
+    }
+}
+
+fn process_keyboard_events(keyboard: KeyboardEvent, buffer: &mut CellBuffer) {
+    let down = if keyboard.event_type == KeyboardEventType::KeyDown {
+        "down"
+    } else {
+        "up"
+    };
+
+    buffer.write_string(
+        &format!("{:?} {}", keyboard.key, down),
+        Point2d::new(0, 2),
+        Color::White,
+        Color::DarkBlue,
+        Style::NONE,
+    );
+
+    buffer.write_str(
+        if keyboard.left_shift {
+            "left shift down"
+        } else {
+            "left shift up  "
+        },
+        Point2d::new(0, 3),
+        Color::White,
+        Color::DarkBlue,
+        Style::NONE,
+    );
+
+    buffer.write_str(
+        if keyboard.left_menu {
+            "left alt down"
+        } else {
+            "left alt up  "
+        },
+        Point2d::new(0, 4),
+        Color::White,
+        Color::DarkBlue,
+        Style::NONE,
+    );
+
+    buffer.write_str(
+        if keyboard.left_control {
+            "left control down"
+        } else {
+            "left control up  "
+        },
+        Point2d::new(0, 5),
+        Color::White,
+        Color::DarkBlue,
+        Style::NONE,
+    );
+
+    buffer.write_str(
+        if keyboard.right_shift {
+            "right shift down"
+        } else {
+            "right shift up  "
+        },
+        Point2d::new(20, 3),
+        Color::White,
+        Color::DarkBlue,
+        Style::NONE,
+    );
+
+    buffer.write_str(
+        if keyboard.right_menu {
+            "right alt down"
+        } else {
+            "right alt up  "
+        },
+        Point2d::new(20, 4),
+        Color::White,
+        Color::DarkBlue,
+        Style::NONE,
+    );
+
+    buffer.write_str(
+        if keyboard.right_control {
+            "right control down"
+        } else {
+            "right control up  "
+        },
+        Point2d::new(20, 5),
+        Color::White,
+        Color::DarkBlue,
+        Style::NONE,
+    );
+}
+
+fn fill_selection(buffer: &mut CellBuffer, drag: Drag, cell: Cell) {
+    buffer.fill_rect(drag.get_rect(), cell);
+}
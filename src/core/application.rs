@@ -1,17 +1,19 @@
 use core::events::event_queue::EventQueue;
+use core::events::event_sender::EventSender;
 use core::input::keyboard_state::KeyboardState;
 use core::input::mouse_state::MouseState;
 use core::mouse::Mouse;
 use core::terminal::Terminal;
 use core::window::Window;
 use core::Result;
+use std::time::Duration;
 
 pub trait Application {
-    fn get_terminal(&self) -> &Terminal;
+    fn get_terminal(&self) -> &dyn Terminal;
 
-    fn get_window(&self) -> &Window;
+    fn get_window(&self) -> &dyn Window;
 
-    fn get_mouse(&self) -> &Mouse;
+    fn get_mouse(&self) -> &dyn Mouse;
 
     fn get_mouse_state(&self) -> &MouseState;
 
@@ -21,5 +23,18 @@ pub trait Application {
 
     fn get_mut_event_queue(&mut self) -> &mut EventQueue;
 
+    /// Gets a sender that other threads can use to add events to the queue.
+    fn get_event_sender(&self) -> EventSender {
+        self.get_event_queue().get_sender()
+    }
+
+    /// Starts a new input frame, forgetting the key and button presses and
+    /// releases of the previous one, and adds the pending native events and
+    /// the events sent from other threads to the queue.
     fn listen_events(&mut self) -> Result<()>;
+
+    /// Listens the native events, sleeping until there is at least one event
+    /// in the event queue or the timeout expires. Without a timeout it waits
+    /// as long as needed.
+    fn wait_events(&mut self, timeout: Option<Duration>) -> Result<()>;
 }
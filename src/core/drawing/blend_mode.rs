@@ -0,0 +1,58 @@
+use core::drawing::color::Color;
+
+/// How a color of a layer cell is combined with the color below it. For the
+/// foreground, the glyph of the layer cell is only drawn with `Replace` and
+/// `Alpha`: the other modes tint or keep the glyph below.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BlendMode {
+    /// The layer color replaces the color below.
+    Replace,
+    /// The color below is kept, with its glyph for the foreground.
+    KeepGlyph,
+    /// Multiplies both colors, darkening the color below.
+    Multiply,
+    /// Adds both colors, lightening the color below.
+    Add,
+    /// Mixes the layer color over the color below with an opacity from 0,
+    /// transparent, to 255, opaque.
+    Alpha(u8),
+}
+
+#[allow(dead_code)]
+impl BlendMode {
+    /// Checks if the glyph of the layer cell replaces the glyph below when
+    /// this mode is used for the foreground.
+    #[inline]
+    pub fn replaces_glyph(self) -> bool {
+        matches!(self, BlendMode::Replace | BlendMode::Alpha(_))
+    }
+
+    /// Combines a layer color with the color below it.
+    pub fn blend(self, below: Color, above: Color) -> Color {
+        match self {
+            BlendMode::Replace => above,
+            BlendMode::KeepGlyph => below,
+            BlendMode::Multiply => combine(below, above, |below, above| {
+                (below as u16 * above as u16 / 255) as u8
+            }),
+            BlendMode::Add => combine(below, above, |below, above| below.saturating_add(above)),
+            BlendMode::Alpha(255) => above,
+            BlendMode::Alpha(0) => below,
+            BlendMode::Alpha(alpha) => combine(below, above, |below, above| {
+                ((above as u16 * alpha as u16 + below as u16 * (255 - alpha as u16)) / 255) as u8
+            }),
+        }
+    }
+}
+
+/// Combines the RGB channels of two colors.
+fn combine<F: Fn(u8, u8) -> u8>(below: Color, above: Color, channel: F) -> Color {
+    let (below_red, below_green, below_blue) = below.to_rgb();
+    let (above_red, above_green, above_blue) = above.to_rgb();
+
+    Color::Rgb(
+        channel(below_red, above_red),
+        channel(below_green, above_green),
+        channel(below_blue, above_blue),
+    )
+}
@@ -0,0 +1,189 @@
+use core::drawing::box_style::{BoxStyle, LIGHT};
+
+/// The box-drawing characters with the weight of their lines going up,
+/// right, down and left, sorted by character.
+const BOX_CHARACTERS: &[(char, [u8; 4])] = &[
+    ('\u{2500}', [0, 1, 0, 1]), // ─
+    ('\u{2501}', [0, 2, 0, 2]), // ━
+    ('\u{2502}', [1, 0, 1, 0]), // │
+    ('\u{2503}', [2, 0, 2, 0]), // ┃
+    ('\u{250C}', [0, 1, 1, 0]), // ┌
+    ('\u{250D}', [0, 2, 1, 0]), // ┍
+    ('\u{250E}', [0, 1, 2, 0]), // ┎
+    ('\u{250F}', [0, 2, 2, 0]), // ┏
+    ('\u{2510}', [0, 0, 1, 1]), // ┐
+    ('\u{2511}', [0, 0, 1, 2]), // ┑
+    ('\u{2512}', [0, 0, 2, 1]), // ┒
+    ('\u{2513}', [0, 0, 2, 2]), // ┓
+    ('\u{2514}', [1, 1, 0, 0]), // └
+    ('\u{2515}', [1, 2, 0, 0]), // ┕
+    ('\u{2516}', [2, 1, 0, 0]), // ┖
+    ('\u{2517}', [2, 2, 0, 0]), // ┗
+    ('\u{2518}', [1, 0, 0, 1]), // ┘
+    ('\u{2519}', [1, 0, 0, 2]), // ┙
+    ('\u{251A}', [2, 0, 0, 1]), // ┚
+    ('\u{251B}', [2, 0, 0, 2]), // ┛
+    ('\u{251C}', [1, 1, 1, 0]), // ├
+    ('\u{251D}', [1, 2, 1, 0]), // ┝
+    ('\u{251E}', [2, 1, 1, 0]), // ┞
+    ('\u{251F}', [1, 1, 2, 0]), // ┟
+    ('\u{2520}', [2, 1, 2, 0]), // ┠
+    ('\u{2521}', [2, 2, 1, 0]), // ┡
+    ('\u{2522}', [1, 2, 2, 0]), // ┢
+    ('\u{2523}', [2, 2, 2, 0]), // ┣
+    ('\u{2524}', [1, 0, 1, 1]), // ┤
+    ('\u{2525}', [1, 0, 1, 2]), // ┥
+    ('\u{2526}', [2, 0, 1, 1]), // ┦
+    ('\u{2527}', [1, 0, 2, 1]), // ┧
+    ('\u{2528}', [2, 0, 2, 1]), // ┨
+    ('\u{2529}', [2, 0, 1, 2]), // ┩
+    ('\u{252A}', [1, 0, 2, 2]), // ┪
+    ('\u{252B}', [2, 0, 2, 2]), // ┫
+    ('\u{252C}', [0, 1, 1, 1]), // ┬
+    ('\u{252D}', [0, 1, 1, 2]), // ┭
+    ('\u{252E}', [0, 2, 1, 1]), // ┮
+    ('\u{252F}', [0, 2, 1, 2]), // ┯
+    ('\u{2530}', [0, 1, 2, 1]), // ┰
+    ('\u{2531}', [0, 1, 2, 2]), // ┱
+    ('\u{2532}', [0, 2, 2, 1]), // ┲
+    ('\u{2533}', [0, 2, 2, 2]), // ┳
+    ('\u{2534}', [1, 1, 0, 1]), // ┴
+    ('\u{2535}', [1, 1, 0, 2]), // ┵
+    ('\u{2536}', [1, 2, 0, 1]), // ┶
+    ('\u{2537}', [1, 2, 0, 2]), // ┷
+    ('\u{2538}', [2, 1, 0, 1]), // ┸
+    ('\u{2539}', [2, 1, 0, 2]), // ┹
+    ('\u{253A}', [2, 2, 0, 1]), // ┺
+    ('\u{253B}', [2, 2, 0, 2]), // ┻
+    ('\u{253C}', [1, 1, 1, 1]), // ┼
+    ('\u{253D}', [1, 1, 1, 2]), // ┽
+    ('\u{253E}', [1, 2, 1, 1]), // ┾
+    ('\u{253F}', [1, 2, 1, 2]), // ┿
+    ('\u{2540}', [2, 1, 1, 1]), // ╀
+    ('\u{2541}', [1, 1, 2, 1]), // ╁
+    ('\u{2542}', [2, 1, 2, 1]), // ╂
+    ('\u{2543}', [2, 1, 1, 2]), // ╃
+    ('\u{2544}', [2, 2, 1, 1]), // ╄
+    ('\u{2545}', [1, 1, 2, 2]), // ╅
+    ('\u{2546}', [1, 2, 2, 1]), // ╆
+    ('\u{2547}', [2, 2, 1, 2]), // ╇
+    ('\u{2548}', [1, 2, 2, 2]), // ╈
+    ('\u{2549}', [2, 1, 2, 2]), // ╉
+    ('\u{254A}', [2, 2, 2, 1]), // ╊
+    ('\u{254B}', [2, 2, 2, 2]), // ╋
+    ('\u{2550}', [0, 3, 0, 3]), // ═
+    ('\u{2551}', [3, 0, 3, 0]), // ║
+    ('\u{2552}', [0, 3, 1, 0]), // ╒
+    ('\u{2553}', [0, 1, 3, 0]), // ╓
+    ('\u{2554}', [0, 3, 3, 0]), // ╔
+    ('\u{2555}', [0, 0, 1, 3]), // ╕
+    ('\u{2556}', [0, 0, 3, 1]), // ╖
+    ('\u{2557}', [0, 0, 3, 3]), // ╗
+    ('\u{2558}', [1, 3, 0, 0]), // ╘
+    ('\u{2559}', [3, 1, 0, 0]), // ╙
+    ('\u{255A}', [3, 3, 0, 0]), // ╚
+    ('\u{255B}', [1, 0, 0, 3]), // ╛
+    ('\u{255C}', [3, 0, 0, 1]), // ╜
+    ('\u{255D}', [3, 0, 0, 3]), // ╝
+    ('\u{255E}', [1, 3, 1, 0]), // ╞
+    ('\u{255F}', [3, 1, 3, 0]), // ╟
+    ('\u{2560}', [3, 3, 3, 0]), // ╠
+    ('\u{2561}', [1, 0, 1, 3]), // ╡
+    ('\u{2562}', [3, 0, 3, 1]), // ╢
+    ('\u{2563}', [3, 0, 3, 3]), // ╣
+    ('\u{2564}', [0, 3, 1, 3]), // ╤
+    ('\u{2565}', [0, 1, 3, 1]), // ╥
+    ('\u{2566}', [0, 3, 3, 3]), // ╦
+    ('\u{2567}', [1, 3, 0, 3]), // ╧
+    ('\u{2568}', [3, 1, 0, 1]), // ╨
+    ('\u{2569}', [3, 3, 0, 3]), // ╩
+    ('\u{256A}', [1, 3, 1, 3]), // ╪
+    ('\u{256B}', [3, 1, 3, 1]), // ╫
+    ('\u{256C}', [3, 3, 3, 3]), // ╬
+    ('\u{2574}', [0, 0, 0, 1]), // ╴
+    ('\u{2575}', [1, 0, 0, 0]), // ╵
+    ('\u{2576}', [0, 1, 0, 0]), // ╶
+    ('\u{2577}', [0, 0, 1, 0]), // ╷
+    ('\u{2578}', [0, 0, 0, 2]), // ╸
+    ('\u{2579}', [2, 0, 0, 0]), // ╹
+    ('\u{257A}', [0, 2, 0, 0]), // ╺
+    ('\u{257B}', [0, 0, 2, 0]), // ╻
+    ('\u{257C}', [0, 2, 0, 1]), // ╼
+    ('\u{257D}', [1, 0, 2, 0]), // ╽
+    ('\u{257E}', [0, 1, 0, 2]), // ╾
+    ('\u{257F}', [2, 0, 1, 0]), // ╿
+];
+
+/// The rounded corners, which connect like the single ones.
+const ROUNDED_CORNERS: &[(char, char)] = &[
+    ('\u{256D}', '\u{250C}'), // ╭ ┌
+    ('\u{256E}', '\u{2510}'), // ╮ ┐
+    ('\u{256F}', '\u{2518}'), // ╯ ┘
+    ('\u{2570}', '\u{2514}'), // ╰ └
+];
+
+/// Gets the weight of the lines of a box-drawing character going up, right,
+/// down and left, or none for the other characters.
+pub fn get_box_connections(character: char) -> Option<[u8; 4]> {
+    let character = ROUNDED_CORNERS
+        .iter()
+        .find(|corner| corner.0 == character)
+        .map_or(character, |corner| corner.1);
+
+    BOX_CHARACTERS
+        .binary_search_by(|entry| entry.0.cmp(&character))
+        .ok()
+        .map(|index| BOX_CHARACTERS[index].1)
+}
+
+/// Gets the box-drawing character with the given lines, if there is one.
+/// The rounded style uses rounded corners.
+pub fn get_box_char(connections: [u8; 4], style: BoxStyle) -> Option<char> {
+    let character = BOX_CHARACTERS
+        .iter()
+        .find(|entry| entry.1 == connections)
+        .map(|entry| entry.0)?;
+
+    if style == BoxStyle::Rounded {
+        if let Some(corner) = ROUNDED_CORNERS.iter().find(|corner| corner.1 == character) {
+            return Some(corner.0);
+        }
+    }
+
+    Some(character)
+}
+
+/// Gets the character that joins the lines already in a cell with new lines
+/// of the given style. Unicode lacks some combinations, like double and heavy
+/// lines together, and then the new style is used for every line.
+pub fn merge_box_char(existing: char, connections: [u8; 4], style: BoxStyle) -> char {
+    let mut merged = connections;
+
+    if let Some(existing) = get_box_connections(existing) {
+        for (weight, existing) in merged.iter_mut().zip(existing.iter()) {
+            if *weight == 0 {
+                *weight = *existing;
+            }
+        }
+    }
+
+    get_box_char(merged, style)
+        .or_else(|| get_box_char(get_uniform(merged, style.get_weight()), style))
+        .or_else(|| get_box_char(get_uniform(merged, LIGHT), style))
+        .or_else(|| get_box_char(connections, style))
+        .unwrap_or('+')
+}
+
+/// Replaces the weight of every line with the given one.
+#[inline]
+fn get_uniform(connections: [u8; 4], weight: u8) -> [u8; 4] {
+    let mut uniform = connections;
+
+    for line in uniform.iter_mut() {
+        if *line != 0 {
+            *line = weight;
+        }
+    }
+
+    uniform
+}
@@ -0,0 +1,28 @@
+/// Enumerates the line styles of the box-drawing frames.
+#[allow(dead_code)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BoxStyle {
+    Single,
+    Double,
+    Heavy,
+
+    /// Single lines with rounded corners.
+    Rounded,
+}
+
+#[allow(dead_code)]
+impl BoxStyle {
+    /// Gets the weight of the lines, as used by the box-drawing connections.
+    pub fn get_weight(self) -> u8 {
+        match self {
+            BoxStyle::Single | BoxStyle::Rounded => LIGHT,
+            BoxStyle::Heavy => HEAVY,
+            BoxStyle::Double => DOUBLE,
+        }
+    }
+}
+
+/// The weights of the box-drawing lines.
+pub const LIGHT: u8 = 1;
+pub const HEAVY: u8 = 2;
+pub const DOUBLE: u8 = 3;
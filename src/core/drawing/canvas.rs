@@ -0,0 +1,713 @@
+use core::drawing::box_drawing::merge_box_char;
+use core::drawing::box_style::BoxStyle;
+use core::drawing::cell::Cell;
+use core::drawing::cell_buffer::CellBuffer;
+use core::drawing::cell_buffer_view::CellBufferView;
+use core::drawing::char_width::get_char_width;
+use core::drawing::color::Color;
+use core::drawing::connectivity::Connectivity;
+use core::drawing::point_2d::Point2d;
+use core::drawing::rect::Rect;
+use core::drawing::size_2d::Size2d;
+use core::drawing::style::Style;
+use std::str::Chars;
+
+/// Something cells can be drawn on. The drawing primitives are built on
+/// `get_cell` and `set_cell`, and everything outside of `get_rect` is
+/// clipped.
+pub trait Canvas {
+    /// Gets the rectangle of the cells that can be drawn.
+    fn get_rect(&self) -> Rect;
+
+    /// Gets a cell, or none when it is outside of the canvas.
+    fn get_cell(&self, position: Point2d) -> Option<Cell>;
+
+    /// Sets a cell, ignoring the positions outside of the canvas.
+    fn set_cell(&mut self, position: Point2d, cell: Cell);
+
+    /// Writes the characters in a row according to their display width:
+    /// wide characters take two cells and combining marks attach to the
+    /// previous character. The text is clipped by the canvas, and a wide
+    /// character cut by an edge leaves a blank cell.
+    fn write_chars(
+        &mut self,
+        text: Chars,
+        position: Point2d,
+        foreground: Color,
+        background: Color,
+        style: Style,
+    ) {
+        let rect = self.get_rect();
+
+        if position.y < rect.get_top() || position.y >= rect.get_bottom() {
+            return;
+        }
+
+        let blank = Cell::new_styled(' ', foreground, background, style);
+        let mut x = position.x;
+        let mut last_position: Option<Point2d> = None;
+
+        for character in text {
+            let character_width = get_char_width(character) as i32;
+
+            if character_width == 0 {
+                if let Some(last_position) = last_position {
+                    if let Some(mut cell) = self.get_cell(last_position) {
+                        cell.add_mark(character);
+                        self.set_cell(last_position, cell);
+                    }
+                }
+                continue;
+            }
+
+            if x >= rect.get_right() {
+                break;
+            }
+
+            last_position = None;
+
+            if character_width == 2 && (x + 1 == rect.get_left() || x + 1 == rect.get_right()) {
+                // only one half of the wide character is visible.
+                self.set_cell(Point2d::new(x.max(rect.get_left()), position.y), blank);
+            } else if x >= rect.get_left() {
+                let cell_position = Point2d::new(x, position.y);
+                let cell = Cell::new_styled(character, foreground, background, style);
+                self.set_cell(cell_position, cell);
+
+                if character_width == 2 {
+                    self.set_cell(cell_position.add_x(1), Cell::new_continuation(&cell));
+                }
+
+                last_position = Some(cell_position);
+            }
+
+            x += character_width;
+        }
+    }
+
+    fn write_str(
+        &mut self,
+        text: &str,
+        position: Point2d,
+        foreground: Color,
+        background: Color,
+        style: Style,
+    ) {
+        self.write_chars(text.chars(), position, foreground, background, style);
+    }
+
+    fn write_string(
+        &mut self,
+        text: &str,
+        position: Point2d,
+        foreground: Color,
+        background: Color,
+        style: Style,
+    ) {
+        self.write_chars(text.chars(), position, foreground, background, style);
+    }
+
+    /// Sets a row of cells, clipped by the canvas.
+    fn repeat_cell(&mut self, cell: Cell, position: Point2d, length: usize) {
+        self.fill_rect(Rect::new(position, Size2d::new(length, 1)), cell);
+    }
+
+    /// Copies another buffer at a position, clipped by the canvas. A wide
+    /// character cut by an edge is replaced by a blank.
+    fn write_cell_buffer(&mut self, cell_buffer: &CellBuffer, position: Point2d) {
+        self.write_view(&cell_buffer.get_view(cell_buffer.get_rect()), position);
+    }
+
+    /// Copies the visible cells of a view at a position, clipped by the
+    /// canvas. A wide character cut by an edge is replaced by a blank.
+    fn write_view(&mut self, view: &CellBufferView, position: Point2d) {
+        let visible = view.get_rect().offset(position).intersect(self.get_rect());
+
+        for destination in visible.iter_cells() {
+            let mut cell = view.get(destination - position);
+
+            if (cell.is_wide() && destination.x + 1 >= visible.get_right())
+                || (cell.continuation && destination.x == visible.get_left())
+            {
+                cell.set_character(' ');
+            }
+
+            self.set_cell(destination, cell);
+        }
+    }
+
+    /// Draws a line between two cells, both included, with the Bresenham
+    /// algorithm.
+    fn draw_line(&mut self, start: Point2d, end: Point2d, cell: Cell) {
+        let delta_x = (end.x - start.x).abs();
+        let delta_y = -(end.y - start.y).abs();
+        let step_x = if start.x < end.x { 1 } else { -1 };
+        let step_y = if start.y < end.y { 1 } else { -1 };
+        let mut error = delta_x + delta_y;
+        let mut position = start;
+
+        loop {
+            self.set_cell(position, cell);
+
+            if position == end {
+                break;
+            }
+
+            let double_error = error * 2;
+
+            if double_error >= delta_y {
+                error += delta_y;
+                position.x += step_x;
+            }
+
+            if double_error <= delta_x {
+                error += delta_x;
+                position.y += step_y;
+            }
+        }
+    }
+
+    fn fill_rect(&mut self, rect: Rect, cell: Cell) {
+        for position in rect.intersect(self.get_rect()).iter_cells() {
+            self.set_cell(position, cell);
+        }
+    }
+
+    /// Draws the cells on the edges of a rectangle.
+    fn draw_rect(&mut self, rect: Rect, cell: Cell) {
+        if rect.is_empty() {
+            return;
+        }
+
+        let right = rect.get_right() - 1;
+        let bottom = rect.get_bottom() - 1;
+
+        self.draw_line(rect.position, Point2d::new(right, rect.get_top()), cell);
+        self.draw_line(
+            Point2d::new(rect.get_left(), bottom),
+            Point2d::new(right, bottom),
+            cell,
+        );
+        self.draw_line(rect.position, Point2d::new(rect.get_left(), bottom), cell);
+        self.draw_line(
+            Point2d::new(right, rect.get_top()),
+            Point2d::new(right, bottom),
+            cell,
+        );
+    }
+
+    /// Draws a frame with box-drawing characters on the edges of a rectangle.
+    /// The colors and style come from the cell, and the lines join the lines
+    /// already drawn where frames meet.
+    fn draw_frame(&mut self, rect: Rect, style: BoxStyle, cell: Cell) {
+        let right = rect.get_right() - 1;
+        let bottom = rect.get_bottom() - 1;
+        let weight = style.get_weight();
+
+        for position in rect.iter_cells() {
+            let horizontal = position.y == rect.get_top() || position.y == bottom;
+            let vertical = position.x == rect.get_left() || position.x == right;
+
+            if !horizontal && !vertical {
+                continue;
+            }
+
+            let connections = [
+                if vertical && position.y > rect.get_top() {
+                    weight
+                } else {
+                    0
+                },
+                if horizontal && position.x < right {
+                    weight
+                } else {
+                    0
+                },
+                if vertical && position.y < bottom {
+                    weight
+                } else {
+                    0
+                },
+                if horizontal && position.x > rect.get_left() {
+                    weight
+                } else {
+                    0
+                },
+            ];
+
+            self.draw_box_connections(position, connections, style, cell);
+        }
+    }
+
+    /// Draws a horizontal or vertical line with box-drawing characters,
+    /// joining the lines already drawn, so a divider that ends on a frame
+    /// gets the junction characters. Other lines are drawn like `draw_line`
+    /// with the character of the cell.
+    fn draw_box_line(&mut self, start: Point2d, end: Point2d, style: BoxStyle, cell: Cell) {
+        if start.x != end.x && start.y != end.y {
+            self.draw_line(start, end, cell);
+            return;
+        }
+
+        let rect = Rect::from_corners(start, end);
+        let horizontal = start.y == end.y && start.x != end.x;
+        let weight = style.get_weight();
+
+        for position in rect.iter_cells() {
+            let connections = if horizontal {
+                [
+                    0,
+                    if position.x < rect.get_right() - 1 {
+                        weight
+                    } else {
+                        0
+                    },
+                    0,
+                    if position.x > rect.get_left() {
+                        weight
+                    } else {
+                        0
+                    },
+                ]
+            } else {
+                [
+                    if position.y > rect.get_top() {
+                        weight
+                    } else {
+                        0
+                    },
+                    0,
+                    if position.y < rect.get_bottom() - 1 {
+                        weight
+                    } else {
+                        0
+                    },
+                    0,
+                ]
+            };
+
+            self.draw_box_connections(position, connections, style, cell);
+        }
+    }
+
+    /// Sets a box-drawing character with the given lines going up, right,
+    /// down and left, merged with the lines already in the cell.
+    fn draw_box_connections(
+        &mut self,
+        position: Point2d,
+        connections: [u8; 4],
+        style: BoxStyle,
+        cell: Cell,
+    ) {
+        let existing = match self.get_cell(position) {
+            Some(existing) => existing,
+            None => return,
+        };
+
+        if connections == [0; 4] {
+            return;
+        }
+
+        let mut cell = cell;
+        cell.set_character(merge_box_char(existing.character, connections, style));
+        self.set_cell(position, cell);
+    }
+
+    fn draw_circle(&mut self, center: Point2d, radius: i32, cell: Cell) {
+        self.draw_ellipse(center, radius, radius, cell);
+    }
+
+    fn fill_circle(&mut self, center: Point2d, radius: i32, cell: Cell) {
+        self.fill_ellipse(center, radius, radius, cell);
+    }
+
+    /// Draws the outline of an ellipse with the midpoint algorithm.
+    fn draw_ellipse(&mut self, center: Point2d, radius_x: i32, radius_y: i32, cell: Cell) {
+        for point in get_ellipse_points(radius_x, radius_y) {
+            for &(x, y) in [(1, 1), (-1, 1), (1, -1), (-1, -1)].iter() {
+                self.set_cell(center.add(Point2d::new(point.x * x, point.y * y)), cell);
+            }
+        }
+    }
+
+    fn fill_ellipse(&mut self, center: Point2d, radius_x: i32, radius_y: i32, cell: Cell) {
+        for point in get_ellipse_points(radius_x, radius_y) {
+            for &y in [point.y, -point.y].iter() {
+                let row = Rect::new(
+                    center.add(Point2d::new(-point.x, y)),
+                    Size2d::new(point.x as usize * 2 + 1, 1),
+                );
+                self.fill_rect(row, cell);
+            }
+        }
+    }
+
+    /// Fills a polygon with the even-odd rule. A cell is filled when its
+    /// center is inside the polygon, with the vertices on cell centers, or
+    /// when it is on an edge drawn like `draw_line`. A polygon with the
+    /// corners of a rectangle fills the same cells as `fill_rect`.
+    fn fill_polygon(&mut self, points: &[Point2d], cell: Cell) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let top = points.iter().map(|point| point.y).min().unwrap_or(0);
+        let bottom = points.iter().map(|point| point.y).max().unwrap_or(0);
+        let rect = self.get_rect();
+        let mut crossings = Vec::new();
+
+        for y in top.max(rect.get_top())..=bottom.min(rect.get_bottom() - 1) {
+            let center = y as f32 + 0.5;
+            crossings.clear();
+
+            for (index, start) in points.iter().enumerate() {
+                let end = points[(index + 1) % points.len()];
+                let (start_y, end_y) = (start.y as f32 + 0.5, end.y as f32 + 0.5);
+
+                if (start_y <= center) != (end_y <= center) {
+                    let ratio = (center - start_y) / (end_y - start_y);
+                    crossings.push(start.x as f32 + ratio * (end.x - start.x) as f32);
+                }
+            }
+
+            crossings.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+            for pair in crossings.chunks(2) {
+                if let [start, end] = *pair {
+                    let start = start.ceil() as i32;
+                    let end = end.floor() as i32;
+
+                    if end >= start {
+                        let row = Rect::new(
+                            Point2d::new(start, y),
+                            Size2d::new((end - start + 1) as usize, 1),
+                        );
+                        self.fill_rect(row, cell);
+                    }
+                }
+            }
+        }
+
+        // the crossings leave out the bottom and right edges.
+        for (index, start) in points.iter().enumerate() {
+            self.draw_line(*start, points[(index + 1) % points.len()], cell);
+        }
+    }
+
+    /// Replaces the cells connected to the start that match the predicate,
+    /// starting with the start cell itself.
+    fn flood_fill<P: FnMut(&Cell) -> bool>(
+        &mut self,
+        start: Point2d,
+        connectivity: Connectivity,
+        mut predicate: P,
+        cell: Cell,
+    ) where
+        Self: Sized,
+    {
+        let rect = self.get_rect();
+        let width = rect.size.width;
+        let mut visited = vec![false; width * rect.size.height];
+        let mut pending = vec![start];
+
+        let neighbours: &[(i32, i32)] = match connectivity {
+            Connectivity::Four => &[(0, -1), (1, 0), (0, 1), (-1, 0)],
+            Connectivity::Eight => &[
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1),
+            ],
+        };
+
+        while let Some(position) = pending.pop() {
+            if !rect.contains(position) {
+                continue;
+            }
+
+            let relative = position - rect.position;
+            let index = relative.x as usize + relative.y as usize * width;
+
+            if visited[index] {
+                continue;
+            }
+
+            visited[index] = true;
+
+            match self.get_cell(position) {
+                Some(existing) if predicate(&existing) => self.set_cell(position, cell),
+                _ => continue,
+            }
+
+            for &(x, y) in neighbours.iter() {
+                pending.push(position.add(Point2d::new(x, y)));
+            }
+        }
+    }
+}
+
+/// Gets the points of the first quadrant of an ellipse centered on the
+/// origin, with the midpoint algorithm.
+fn get_ellipse_points(radius_x: i32, radius_y: i32) -> Vec<Point2d> {
+    let mut points = Vec::new();
+
+    if radius_x < 0 || radius_y < 0 {
+        return points;
+    }
+
+    // a zero radius flattens the ellipse into a line or a single point.
+    if radius_x == 0 || radius_y == 0 {
+        points.extend((0..=radius_x).map(|x| Point2d::new(x, 0)));
+        points.extend((1..=radius_y).map(|y| Point2d::new(0, y)));
+        return points;
+    }
+
+    let rx2 = radius_x as i64 * radius_x as i64;
+    let ry2 = radius_y as i64 * radius_y as i64;
+    let mut x = 0i64;
+    let mut y = radius_y as i64;
+
+    // the region where the slope is below 1.
+    let mut decision = ry2 - rx2 * radius_y as i64 + rx2 / 4;
+
+    while x <= radius_x as i64 && ry2 * x <= rx2 * y {
+        points.push(Point2d::new(x as i32, y as i32));
+
+        if decision < 0 {
+            decision += ry2 * (2 * x + 3);
+        } else {
+            decision += ry2 * (2 * x + 3) - rx2 * (2 * y - 2);
+            y -= 1;
+        }
+
+        x += 1;
+    }
+
+    // the region where the slope is above 1.
+    let mut decision = ry2 * (2 * x + 1) * (2 * x + 1) / 4 + rx2 * (y - 1) * (y - 1) - rx2 * ry2;
+
+    while y >= 0 {
+        points.push(Point2d::new(x as i32, y as i32));
+
+        if decision > 0 {
+            decision += rx2 * (3 - 2 * y);
+        } else {
+            decision += ry2 * (2 * x + 2) + rx2 * (3 - 2 * y);
+            x += 1;
+        }
+
+        y -= 1;
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::box_style::BoxStyle;
+    use core::drawing::canvas::Canvas;
+    use core::drawing::cell::Cell;
+    use core::drawing::cell_buffer::CellBuffer;
+    use core::drawing::connectivity::Connectivity;
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::rect::Rect;
+    use core::drawing::size_2d::Size2d;
+
+    fn create_buffer() -> CellBuffer {
+        CellBuffer::new(Cell::new_default('.'), Size2d::new(12, 8))
+    }
+
+    fn get_marked(buffer: &CellBuffer) -> Vec<Point2d> {
+        buffer
+            .get_rect()
+            .iter_cells()
+            .filter(|position| buffer.get(*position).character == '#')
+            .collect()
+    }
+
+    fn get_row(buffer: &CellBuffer, row: i32) -> String {
+        (0..buffer.size.width as i32)
+            .map(|x| buffer.get(Point2d::new(x, row)).character)
+            .collect()
+    }
+
+    #[test]
+    fn line_includes_both_ends() {
+        let mut buffer = create_buffer();
+        buffer.draw_line(
+            Point2d::new(6, 3),
+            Point2d::new(0, 1),
+            Cell::new_default('#'),
+        );
+
+        assert_eq!(get_row(&buffer, 1), "##..........");
+        assert_eq!(get_row(&buffer, 2), "..###.......");
+        assert_eq!(get_row(&buffer, 3), ".....##.....");
+    }
+
+    #[test]
+    fn line_is_clipped_by_the_buffer() {
+        let mut buffer = create_buffer();
+        buffer.draw_line(
+            Point2d::new(-3, 2),
+            Point2d::new(14, 2),
+            Cell::new_default('#'),
+        );
+
+        assert_eq!(get_row(&buffer, 2), "############");
+    }
+
+    #[test]
+    fn frames_that_share_an_edge_are_joined() {
+        let mut buffer = create_buffer();
+        let cell = Cell::new_default(' ');
+        buffer.draw_frame(
+            Rect::new(Point2d::new(0, 0), Size2d::new(5, 3)),
+            BoxStyle::Single,
+            cell,
+        );
+        buffer.draw_frame(
+            Rect::new(Point2d::new(4, 0), Size2d::new(5, 3)),
+            BoxStyle::Single,
+            cell,
+        );
+
+        assert_eq!(get_row(&buffer, 0), "┌───┬───┐...");
+        assert_eq!(get_row(&buffer, 1), "│...│...│...");
+        assert_eq!(get_row(&buffer, 2), "└───┴───┘...");
+    }
+
+    #[test]
+    fn divider_ends_on_junctions() {
+        let mut buffer = create_buffer();
+        let cell = Cell::new_default(' ');
+        buffer.draw_frame(Rect::from_size(Size2d::new(5, 4)), BoxStyle::Double, cell);
+        buffer.draw_box_line(
+            Point2d::new(0, 2),
+            Point2d::new(4, 2),
+            BoxStyle::Double,
+            cell,
+        );
+
+        assert_eq!(get_row(&buffer, 1), "║...║.......");
+        assert_eq!(get_row(&buffer, 2), "╠═══╣.......");
+    }
+
+    #[test]
+    fn flood_fill_stops_at_walls() {
+        let mut buffer = create_buffer();
+        let wall = Cell::new_default('|');
+        buffer.draw_rect(Rect::from_size(Size2d::new(5, 5)), wall);
+        buffer.flood_fill(
+            Point2d::new(2, 2),
+            Connectivity::Four,
+            |cell| cell.character == '.',
+            Cell::new_default('#'),
+        );
+
+        assert_eq!(
+            get_marked(&buffer),
+            Rect::new(Point2d::new(1, 1), Size2d::new(3, 3))
+                .iter_cells()
+                .collect::<Vec<Point2d>>()
+        );
+    }
+
+    #[test]
+    fn flood_fill_eight_crosses_diagonal_gaps() {
+        let wall = Cell::new_default('|');
+        let mut four = create_buffer();
+        four.draw_line(Point2d::new(0, 3), Point2d::new(3, 0), wall);
+        four.flood_fill(
+            Point2d::new(0, 0),
+            Connectivity::Four,
+            |cell| cell.character == '.',
+            Cell::new_default('#'),
+        );
+
+        let mut eight = create_buffer();
+        eight.draw_line(Point2d::new(0, 3), Point2d::new(3, 0), wall);
+        eight.flood_fill(
+            Point2d::new(0, 0),
+            Connectivity::Eight,
+            |cell| cell.character == '.',
+            Cell::new_default('#'),
+        );
+
+        assert_eq!(get_marked(&four).len(), 6);
+        assert_eq!(get_marked(&eight).len(), 12 * 8 - 4);
+    }
+
+    #[test]
+    fn zero_radius_circle_is_a_point() {
+        let mut buffer = create_buffer();
+        buffer.draw_circle(Point2d::new(3, 3), 0, Cell::new_default('#'));
+        assert_eq!(get_marked(&buffer), vec![Point2d::new(3, 3)]);
+
+        let mut buffer = create_buffer();
+        buffer.fill_circle(Point2d::new(3, 3), 0, Cell::new_default('#'));
+        assert_eq!(get_marked(&buffer), vec![Point2d::new(3, 3)]);
+    }
+
+    #[test]
+    fn zero_radius_ellipse_is_a_line() {
+        let mut buffer = create_buffer();
+        buffer.draw_ellipse(Point2d::new(5, 4), 0, 2, Cell::new_default('#'));
+        let line: Vec<Point2d> = (2..=6).map(|y| Point2d::new(5, y)).collect();
+        assert_eq!(get_marked(&buffer), line);
+
+        let mut buffer = create_buffer();
+        buffer.fill_ellipse(Point2d::new(5, 4), 3, 0, Cell::new_default('#'));
+        let line: Vec<Point2d> = (2..=8).map(|x| Point2d::new(x, 4)).collect();
+        assert_eq!(get_marked(&buffer), line);
+    }
+
+    #[test]
+    fn circle_is_symmetric() {
+        let mut buffer = create_buffer();
+        buffer.draw_circle(Point2d::new(5, 3), 3, Cell::new_default('#'));
+        let marked = get_marked(&buffer);
+
+        assert!(marked.contains(&Point2d::new(5, 0)));
+        assert!(marked.contains(&Point2d::new(5, 6)));
+        assert!(marked.contains(&Point2d::new(2, 3)));
+        assert!(marked.contains(&Point2d::new(8, 3)));
+        assert!(!marked.contains(&Point2d::new(5, 3)));
+    }
+
+    #[test]
+    fn rectangle_polygon_fills_like_fill_rect() {
+        let corners = [
+            Point2d::new(1, 0),
+            Point2d::new(8, 0),
+            Point2d::new(8, 5),
+            Point2d::new(1, 5),
+        ];
+        let mut polygon = create_buffer();
+        polygon.fill_polygon(&corners, Cell::new_default('#'));
+
+        let mut rect = create_buffer();
+        let corners = Rect::from_corners(Point2d::new(1, 0), Point2d::new(8, 5));
+        rect.fill_rect(corners, Cell::new_default('#'));
+
+        assert_eq!(get_marked(&polygon), get_marked(&rect));
+    }
+
+    #[test]
+    fn triangle_polygon_keeps_its_bottom_row() {
+        let mut buffer = create_buffer();
+        let corners = [Point2d::new(0, 6), Point2d::new(5, 1), Point2d::new(10, 6)];
+        buffer.fill_polygon(&corners, Cell::new_default('#'));
+
+        for x in 0..=10 {
+            assert_eq!(buffer.get(Point2d::new(x, 6)).character, '#');
+        }
+
+        assert_eq!(buffer.get(Point2d::new(5, 1)).character, '#');
+        assert_eq!(buffer.get(Point2d::new(0, 5)).character, '.');
+    }
+}
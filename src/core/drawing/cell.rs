@@ -1,27 +1,94 @@
+use core::drawing::char_width::get_char_width;
 use core::drawing::color::Color;
+use core::drawing::style::Style;
 
-#[derive(Debug, Copy, Clone)]
+/// The maximum amount of combining marks a cell keeps.
+pub const MAX_MARKS: usize = 2;
+
+/// A character cell. Wide characters take two cells: the first one keeps the
+/// character and the second one is a continuation cell that only keeps the
+/// colors. Combining marks are stored in the cell of the character they modify.
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Cell {
     pub character: char,
+    pub marks: [Option<char>; MAX_MARKS],
+    pub continuation: bool,
     pub background: Color,
     pub foreground: Color,
+    pub style: Style,
 }
 
 #[allow(dead_code)]
 impl Cell {
     pub fn new(character: char, foreground: Color, background: Color) -> Cell {
+        Cell::new_styled(character, foreground, background, Style::NONE)
+    }
+
+    pub fn new_styled(character: char, foreground: Color, background: Color, style: Style) -> Cell {
         Cell {
             character,
+            marks: [None; MAX_MARKS],
+            continuation: false,
             background,
             foreground,
+            style,
         }
     }
 
     pub fn new_default(character: char) -> Cell {
+        Cell::new(character, Color::Grey, Color::Black)
+    }
+
+    /// Creates the cell that follows a wide character, with its colors and style.
+    pub fn new_continuation(lead: &Cell) -> Cell {
         Cell {
-            character,
-            background: Color::Black,
-            foreground: Color::Grey,
+            character: ' ',
+            marks: [None; MAX_MARKS],
+            continuation: true,
+            background: lead.background,
+            foreground: lead.foreground,
+            style: lead.style,
+        }
+    }
+
+    /// Gets the amount of columns the character of the cell takes.
+    /// Continuation cells take none, as they belong to the previous cell.
+    #[inline]
+    pub fn get_width(&self) -> usize {
+        if self.continuation {
+            0
+        } else {
+            get_char_width(self.character).max(1)
         }
     }
+
+    #[inline]
+    pub fn is_wide(&self) -> bool {
+        self.get_width() == 2
+    }
+
+    /// Attaches a combining mark to the character. Returns false when the
+    /// cell can't keep more marks.
+    pub fn add_mark(&mut self, mark: char) -> bool {
+        match self.marks.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                *slot = Some(mark);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Iterates the combining marks attached to the character.
+    pub fn get_marks(&self) -> impl Iterator<Item = char> + '_ {
+        self.marks.iter().filter_map(|mark| *mark)
+    }
+
+    /// Replaces the character, removing the previous marks.
+    #[inline]
+    pub fn set_character(&mut self, character: char) {
+        self.character = character;
+        self.marks = [None; MAX_MARKS];
+        self.continuation = false;
+    }
 }
@@ -0,0 +1,51 @@
+use core::drawing::blend_mode::BlendMode;
+use core::drawing::cell::Cell;
+
+/// The blend modes of the foreground and background of a layer cell.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CellBlend {
+    pub foreground: BlendMode,
+    pub background: BlendMode,
+}
+
+#[allow(dead_code)]
+impl CellBlend {
+    pub fn new(foreground: BlendMode, background: BlendMode) -> CellBlend {
+        CellBlend {
+            foreground,
+            background,
+        }
+    }
+
+    /// Creates the blend of cells that cover the cells below.
+    pub fn opaque() -> CellBlend {
+        CellBlend::new(BlendMode::Replace, BlendMode::Replace)
+    }
+
+    /// Creates a blend that only tints the background, keeping the glyphs
+    /// below, like lighting or hover highlights.
+    pub fn background_only(background: BlendMode) -> CellBlend {
+        CellBlend::new(BlendMode::KeepGlyph, background)
+    }
+
+    /// Combines a layer cell with the cell below it. The glyph, marks and
+    /// style come from the layer cell when the foreground mode replaces the
+    /// glyph, and from the cell below otherwise.
+    pub fn apply(&self, below: Cell, above: Cell) -> Cell {
+        let mut cell = if self.foreground.replaces_glyph() {
+            above
+        } else {
+            below
+        };
+
+        cell.foreground = self.foreground.blend(below.foreground, above.foreground);
+        cell.background = self.background.blend(below.background, above.background);
+        cell
+    }
+}
+
+impl Default for CellBlend {
+    fn default() -> CellBlend {
+        CellBlend::opaque()
+    }
+}
@@ -1,22 +1,31 @@
+use core::drawing::canvas::Canvas;
 use core::drawing::cell::Cell;
-use core::drawing::color::Color;
+use core::drawing::cell_buffer_view::CellBufferView;
+use core::drawing::cell_buffer_view_mut::CellBufferViewMut;
 use core::drawing::point_2d::Point2d;
+use core::drawing::rect::Rect;
+use core::drawing::row_span::RowSpan;
 use core::drawing::size_2d::Size2d;
-use std::slice::Iter;
-use std::str::Chars;
+use std::slice::{Iter, IterMut};
 
-#[derive(Debug)]
+/// A grid of cells. The buffer tracks the cells that change, as a span per
+/// row, so a renderer can send only them after `take_dirty_regions`.
+#[derive(Debug, Clone)]
 pub struct CellBuffer {
     pub size: Size2d,
     cells: Vec<Cell>,
+    dirty_rows: Vec<Option<(usize, usize)>>,
 }
 
 #[allow(dead_code)]
 impl CellBuffer {
+    /// Creates a buffer filled with a cell. Every cell of a new buffer is
+    /// dirty.
     pub fn new(default_cell: Cell, size: Size2d) -> CellBuffer {
         CellBuffer {
             size,
             cells: vec![default_cell; size.width * size.height],
+            dirty_rows: vec![Some((0, size.width)); size.height],
         }
     }
 
@@ -24,14 +33,135 @@ impl CellBuffer {
         self.cells.iter()
     }
 
+    /// Iterates the cells mutably. The changes can't be tracked, so every
+    /// cell becomes dirty.
+    pub fn iter_mut(&mut self) -> IterMut<'_, Cell> {
+        self.mark_all_dirty();
+        self.cells.iter_mut()
+    }
+
+    /// Resizes the buffer, filling it with a cell. Every cell becomes dirty.
     pub fn resize(&mut self, default_cell: Cell, new_size: Size2d) {
         self.size = new_size;
         self.cells = vec![default_cell; new_size.width * new_size.height];
+        self.mark_all_dirty();
+    }
+
+    /// Copies the size and content of another buffer, reusing the cell storage.
+    /// Every cell becomes dirty.
+    pub fn copy_from(&mut self, cell_buffer: &CellBuffer) {
+        self.size = cell_buffer.size;
+        self.cells.clone_from(&cell_buffer.cells);
+        self.mark_all_dirty();
+    }
+
+    /// Marks every cell as dirty, like after a resize, so the whole buffer
+    /// is drawn again.
+    pub fn mark_all_dirty(&mut self) {
+        self.dirty_rows.clear();
+        self.dirty_rows
+            .resize(self.size.height, Some((0, self.size.width)));
+    }
+
+    /// Checks if a cell changed since the last `take_dirty_regions` call.
+    pub fn has_dirty_regions(&self) -> bool {
+        self.dirty_rows.iter().any(|row| row.is_some())
+    }
+
+    /// Gets, for every row with cells that changed since the previous call,
+    /// the span that goes from the first to the last changed cell, and
+    /// marks every cell as clean. Like `get_changed_spans`, spans never
+    /// split a wide character from its continuation cell.
+    pub fn take_dirty_regions(&mut self) -> Vec<RowSpan> {
+        let width = self.size.width;
+        let mut spans = Vec::new();
+
+        for (row, dirty_row) in self.dirty_rows.iter_mut().enumerate() {
+            let (mut start, mut end) = match dirty_row.take() {
+                Some(span) => span,
+                None => continue,
+            };
+
+            if start > 0 && self.cells[start + row * width].continuation {
+                start -= 1;
+            }
+
+            if end < width && self.cells[end + row * width].continuation {
+                end += 1;
+            }
+
+            spans.push(RowSpan::new(row, start, end));
+        }
+
+        spans
+    }
+
+    /// Gets, for every row that differs from a previous buffer of the same
+    /// size, the span that goes from the first to the last changed cell.
+    /// Spans never split a wide character from its continuation cell.
+    /// Buffers of different sizes differ in every row.
+    pub fn get_changed_spans(&self, previous: &CellBuffer) -> Vec<RowSpan> {
+        let width = self.size.width;
+
+        if !self.size.equal_to(previous.size) {
+            return (0..self.size.height)
+                .map(|row| RowSpan::new(row, 0, width))
+                .collect();
+        }
+
+        let mut spans = Vec::new();
+
+        if width == 0 {
+            return spans;
+        }
+
+        for (row, (cells, previous_cells)) in self
+            .cells
+            .chunks(width)
+            .zip(previous.cells.chunks(width))
+            .enumerate()
+        {
+            let mut start = match cells.iter().zip(previous_cells).position(|(a, b)| a != b) {
+                Some(start) => start,
+                None => continue,
+            };
+
+            let mut end = width
+                - cells
+                    .iter()
+                    .rev()
+                    .zip(previous_cells.iter().rev())
+                    .position(|(a, b)| a != b)
+                    .unwrap_or(0);
+
+            if start > 0 && (cells[start].continuation || previous_cells[start].continuation) {
+                start -= 1;
+            }
+
+            if end < width && (cells[end].continuation || previous_cells[end].continuation) {
+                end += 1;
+            }
+
+            spans.push(RowSpan::new(row, start, end));
+        }
+
+        spans
+    }
+
+    /// Gets the rectangle of the buffer, which clips every drawing call.
+    #[inline]
+    pub fn get_rect(&self) -> Rect {
+        Rect::from_size(self.size)
     }
 
+    /// Gets the index of a cell, or none when it is outside of the buffer.
     #[inline]
-    pub fn index_of(&self, position: Point2d) -> usize {
-        position.x + self.size.width * position.y
+    pub fn index_of(&self, position: Point2d) -> Option<usize> {
+        if !self.get_rect().contains(position) {
+            return None;
+        }
+
+        Some(position.x as usize + self.size.width * position.y as usize)
     }
 
     #[inline]
@@ -40,101 +170,215 @@ impl CellBuffer {
             return Point2d::empty();
         }
 
-        Point2d::new(index % self.size.width, index / self.size.width)
+        Point2d::new(
+            (index % self.size.width) as i32,
+            (index / self.size.width) as i32,
+        )
     }
 
+    /// Gets a cell. Panics when the position is outside of the buffer.
     #[inline]
     pub fn get(&self, position: Point2d) -> Cell {
-        let index = self.index_of(position);
-        assert!(index < self.cells.len());
-        self.cells[index]
+        match self.index_of(position) {
+            Some(index) => self.cells[index],
+            None => panic!("The position {:?} is outside of the buffer.", position),
+        }
     }
 
+    /// Sets a cell. A wide character needs a continuation cell after it,
+    /// which `write_chars` adds, and overwriting half of a wide character
+    /// blanks the other half.
     #[inline]
     pub fn set(&mut self, position: Point2d, cell: Cell) {
-        let index = self.index_of(position);
-        if index >= self.cells.len() {
+        if let Some(index) = self.index_of(position) {
+            self.set_index(index, cell);
+        }
+    }
+
+    /// Gets a read-only view of a rectangle of the buffer, with its own
+    /// coordinates.
+    pub fn get_view(&self, rect: Rect) -> CellBufferView<'_> {
+        CellBufferView::new(self, rect)
+    }
+
+    /// Gets a view that draws in a rectangle of the buffer, with its own
+    /// coordinates and clipped by the rectangle.
+    pub fn get_view_mut(&mut self, rect: Rect) -> CellBufferViewMut<'_> {
+        CellBufferViewMut::new(self, rect)
+    }
+
+    /// Stores a cell, blanking the half of a wide character it breaks.
+    fn set_index(&mut self, index: usize, cell: Cell) {
+        let x = index % self.size.width;
+        let previous = self.cells[index];
+
+        if previous == cell {
             return;
         }
+
+        if previous.continuation && !cell.continuation && x > 0 && self.cells[index - 1].is_wide() {
+            self.cells[index - 1].set_character(' ');
+            self.mark_dirty(index - 1);
+        }
+
+        if previous.is_wide()
+            && !cell.is_wide()
+            && x + 1 < self.size.width
+            && self.cells[index + 1].continuation
+        {
+            self.cells[index + 1].set_character(' ');
+            self.mark_dirty(index + 1);
+        }
+
         self.cells[index] = cell;
+        self.mark_dirty(index);
     }
 
-    pub fn write_chars(
-        &mut self,
-        text: Chars,
-        position: Point2d,
-        foreground: Color,
-        background: Color,
-    ) {
-        let mut index = 0;
+    /// Grows the dirty span of the row of a cell to include it.
+    fn mark_dirty(&mut self, index: usize) {
+        let x = index % self.size.width;
+        let dirty_row = &mut self.dirty_rows[index / self.size.width];
 
-        for character in text {
-            let buffer_index = self.index_of(position.add_x(index));
+        *dirty_row = match *dirty_row {
+            Some((start, end)) => Some((start.min(x), end.max(x + 1))),
+            None => Some((x, x + 1)),
+        };
+    }
+}
 
-            if buffer_index >= self.cells.len() {
-                break;
-            }
+impl Canvas for CellBuffer {
+    #[inline]
+    fn get_rect(&self) -> Rect {
+        CellBuffer::get_rect(self)
+    }
 
-            self.cells[buffer_index].character = character;
-            self.cells[buffer_index].foreground = foreground;
-            self.cells[buffer_index].background = background;
+    #[inline]
+    fn get_cell(&self, position: Point2d) -> Option<Cell> {
+        self.index_of(position).map(|index| self.cells[index])
+    }
 
-            index += 1;
-        }
+    #[inline]
+    fn set_cell(&mut self, position: Point2d, cell: Cell) {
+        self.set(position, cell);
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::canvas::Canvas;
+    use core::drawing::cell::Cell;
+    use core::drawing::cell_buffer::CellBuffer;
+    use core::drawing::color::Color;
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::row_span::RowSpan;
+    use core::drawing::size_2d::Size2d;
+    use core::drawing::style::Style;
 
-    pub fn write_str(
-        &mut self,
-        text: &str,
-        position: Point2d,
-        foreground: Color,
-        background: Color,
-    ) {
-        self.write_chars(text.chars(), position, foreground, background);
+    fn create_buffer() -> CellBuffer {
+        let mut buffer = CellBuffer::new(Cell::new_default('.'), Size2d::new(8, 3));
+        buffer.take_dirty_regions();
+        buffer
     }
 
-    pub fn write_string(
-        &mut self,
-        text: &String,
-        position: Point2d,
-        foreground: Color,
-        background: Color,
-    ) {
-        self.write_chars(text.chars(), position, foreground, background);
+    fn get_row(buffer: &CellBuffer, row: i32) -> String {
+        (0..buffer.size.width as i32)
+            .map(|x| buffer.get(Point2d::new(x, row)).character)
+            .collect()
     }
 
-    pub fn repeat_cell(&mut self, cell: Cell, position: Point2d, length: usize) {
-        for index in 0..length {
-            let buffer_index = self.index_of(position.add_x(index));
+    fn write(buffer: &mut CellBuffer, text: &str, position: Point2d) {
+        buffer.write_str(text, position, Color::Grey, Color::Black, Style::NONE);
+    }
 
-            if buffer_index >= self.cells.len() {
-                break;
-            }
+    #[test]
+    fn new_buffer_is_dirty() {
+        let mut buffer = CellBuffer::new(Cell::new_default('.'), Size2d::new(4, 2));
 
-            self.cells[buffer_index] = cell;
-        }
+        assert!(buffer.has_dirty_regions());
+        assert_eq!(
+            buffer.take_dirty_regions(),
+            vec![RowSpan::new(0, 0, 4), RowSpan::new(1, 0, 4)]
+        );
+        assert!(!buffer.has_dirty_regions());
+        assert!(buffer.take_dirty_regions().is_empty());
     }
 
-    pub fn write_cell_buffer(&mut self, cell_buffer: &CellBuffer, position: Point2d) {
-        for cby in 0..cell_buffer.size.height {
-            let destination_y = position.y + cby;
+    #[test]
+    fn dirty_region_spans_the_changed_cells() {
+        let mut buffer = create_buffer();
+        buffer.set(Point2d::new(5, 1), Cell::new_default('b'));
+        buffer.set(Point2d::new(2, 1), Cell::new_default('a'));
 
-            if destination_y >= self.size.height {
-                break;
-            }
+        assert_eq!(buffer.take_dirty_regions(), vec![RowSpan::new(1, 2, 6)]);
+    }
 
-            for cbx in 0..cell_buffer.size.width {
-                let destination_x = position.x + cbx;
+    #[test]
+    fn identical_cells_are_not_dirty() {
+        let mut buffer = create_buffer();
+        buffer.set(Point2d::new(3, 0), Cell::new_default('.'));
+        write(&mut buffer, "..", Point2d::new(0, 2));
 
-                if destination_x >= self.size.width {
-                    break;
-                }
+        assert!(!buffer.has_dirty_regions());
+    }
 
-                self.set(
-                    Point2d::new(destination_x, destination_y),
-                    cell_buffer.get(Point2d::new(cbx, cby)),
-                );
-            }
-        }
+    #[test]
+    fn dirty_region_keeps_wide_characters_whole() {
+        let mut buffer = create_buffer();
+        write(&mut buffer, "界", Point2d::new(2, 0));
+        buffer.take_dirty_regions();
+
+        // only the lead cell changes, the continuation cell is identical.
+        write(&mut buffer, "国", Point2d::new(2, 0));
+        assert_eq!(buffer.take_dirty_regions(), vec![RowSpan::new(0, 2, 4)]);
+    }
+
+    #[test]
+    fn breaking_a_wide_character_blanks_the_other_half() {
+        let mut buffer = create_buffer();
+        write(&mut buffer, "界", Point2d::new(2, 0));
+        buffer.take_dirty_regions();
+
+        buffer.set(Point2d::new(3, 0), Cell::new_default('x'));
+        assert_eq!(get_row(&buffer, 0), ".. x....");
+    }
+
+    #[test]
+    fn breaking_a_wide_character_dirties_both_halves() {
+        let mut buffer = create_buffer();
+        write(&mut buffer, "界", Point2d::new(2, 0));
+        buffer.take_dirty_regions();
+
+        buffer.set(Point2d::new(3, 0), Cell::new_default('x'));
+        assert_eq!(buffer.take_dirty_regions(), vec![RowSpan::new(0, 2, 4)]);
+    }
+
+    #[test]
+    fn resize_makes_everything_dirty() {
+        let mut buffer = create_buffer();
+        buffer.resize(Cell::new_default(' '), Size2d::new(3, 1));
+
+        assert_eq!(buffer.take_dirty_regions(), vec![RowSpan::new(0, 0, 3)]);
+    }
+
+    #[test]
+    fn write_chars_is_clipped_by_the_buffer() {
+        let mut buffer = create_buffer();
+        write(&mut buffer, "abcd", Point2d::new(-2, 0));
+        write(&mut buffer, "efgh", Point2d::new(6, 1));
+        write(&mut buffer, "ijkl", Point2d::new(0, 3));
+
+        assert_eq!(get_row(&buffer, 0), "cd......");
+        assert_eq!(get_row(&buffer, 1), "......ef");
+        assert_eq!(get_row(&buffer, 2), "........");
+    }
+
+    #[test]
+    fn wide_character_cut_by_an_edge_is_blank() {
+        let mut buffer = create_buffer();
+        write(&mut buffer, "a界", Point2d::new(6, 0));
+        write(&mut buffer, "界b", Point2d::new(-1, 1));
+
+        assert_eq!(get_row(&buffer, 0), "......a ");
+        assert_eq!(get_row(&buffer, 1), " b......");
     }
 }
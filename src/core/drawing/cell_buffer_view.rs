@@ -0,0 +1,132 @@
+use core::drawing::cell::Cell;
+use core::drawing::cell_buffer::CellBuffer;
+use core::drawing::point_2d::Point2d;
+use core::drawing::rect::Rect;
+use core::drawing::size_2d::Size2d;
+
+/// A read-only view of a rectangle of a cell buffer. The view has its own
+/// coordinates, with its top left corner at the origin, and hides the cells
+/// outside of its rectangle.
+#[derive(Debug, Copy, Clone)]
+pub struct CellBufferView<'a> {
+    buffer: &'a CellBuffer,
+    origin: Point2d,
+    size: Size2d,
+    clip: Rect,
+}
+
+#[allow(dead_code)]
+impl<'a> CellBufferView<'a> {
+    /// Creates a view of a rectangle of the buffer, which can be partially
+    /// or completely outside of it.
+    pub fn new(buffer: &'a CellBuffer, rect: Rect) -> CellBufferView<'a> {
+        CellBufferView::new_clipped(buffer, rect, rect)
+    }
+
+    /// Creates a view of a rectangle of the buffer that also hides the cells
+    /// outside of a clip rectangle, both in buffer coordinates.
+    pub fn new_clipped(buffer: &'a CellBuffer, rect: Rect, clip: Rect) -> CellBufferView<'a> {
+        CellBufferView {
+            buffer,
+            origin: rect.position,
+            size: rect.size,
+            clip: clip.intersect(rect).intersect(buffer.get_rect()),
+        }
+    }
+
+    /// Gets a view of a rectangle of this view, in the coordinates of this
+    /// view. The nested view is clipped by this one.
+    pub fn get_view(&self, rect: Rect) -> CellBufferView<'a> {
+        CellBufferView::new_clipped(self.buffer, rect.offset(self.origin), self.clip)
+    }
+
+    /// Gets the size of the view, including the hidden cells.
+    #[inline]
+    pub fn get_size(&self) -> Size2d {
+        self.size
+    }
+
+    /// Gets the position of the top left corner of the view in the buffer.
+    #[inline]
+    pub fn get_origin(&self) -> Point2d {
+        self.origin
+    }
+
+    /// Gets the rectangle of the visible cells, in view coordinates.
+    #[inline]
+    pub fn get_rect(&self) -> Rect {
+        self.clip.offset(-self.origin)
+    }
+
+    /// Gets a cell, or none when it is hidden.
+    #[inline]
+    pub fn get_cell(&self, position: Point2d) -> Option<Cell> {
+        let position = position + self.origin;
+
+        if self.clip.contains(position) {
+            Some(self.buffer.get(position))
+        } else {
+            None
+        }
+    }
+
+    /// Gets a cell. Panics when the cell is hidden.
+    #[inline]
+    pub fn get(&self, position: Point2d) -> Cell {
+        match self.get_cell(position) {
+            Some(cell) => cell,
+            None => panic!("The position {:?} is outside of the view.", position),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::cell::Cell;
+    use core::drawing::cell_buffer::CellBuffer;
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::rect::Rect;
+    use core::drawing::size_2d::Size2d;
+
+    fn create_buffer() -> CellBuffer {
+        let mut buffer = CellBuffer::new(Cell::new_default('.'), Size2d::new(4, 3));
+        buffer.set(Point2d::new(0, 0), Cell::new_default('a'));
+        buffer.set(Point2d::new(3, 2), Cell::new_default('b'));
+        buffer
+    }
+
+    #[test]
+    fn nested_views_read_through_both_offsets() {
+        let buffer = create_buffer();
+        let view = buffer.get_view(Rect::new(Point2d::new(1, 1), Size2d::new(3, 2)));
+        let nested = view.get_view(Rect::new(Point2d::new(1, 0), Size2d::new(2, 2)));
+
+        assert_eq!(nested.get_origin(), Point2d::new(2, 1));
+        assert_eq!(nested.get(Point2d::new(1, 1)).character, 'b');
+    }
+
+    #[test]
+    fn cells_outside_of_the_view_are_hidden() {
+        let buffer = create_buffer();
+        let view = buffer.get_view(Rect::new(Point2d::new(1, 1), Size2d::new(2, 2)));
+        let nested = view.get_view(Rect::new(Point2d::new(1, 1), Size2d::new(5, 5)));
+
+        assert!(view.get_cell(Point2d::new(-1, -1)).is_none());
+        assert!(view.get_cell(Point2d::new(2, 1)).is_none());
+        assert_eq!(nested.get_rect(), Rect::from_size(Size2d::new(1, 1)));
+        assert!(nested.get_cell(Point2d::new(1, 0)).is_none());
+    }
+
+    #[test]
+    fn negative_origin_reads_the_visible_cells() {
+        let buffer = create_buffer();
+        let view = buffer.get_view(Rect::new(Point2d::new(-1, -2), Size2d::new(3, 3)));
+
+        assert_eq!(
+            view.get_rect(),
+            Rect::new(Point2d::new(1, 2), Size2d::new(2, 1))
+        );
+        assert_eq!(view.get(Point2d::new(1, 2)).character, 'a');
+        assert!(view.get_cell(Point2d::new(0, 2)).is_none());
+    }
+}
@@ -0,0 +1,221 @@
+use core::drawing::canvas::Canvas;
+use core::drawing::cell::Cell;
+use core::drawing::cell_buffer::CellBuffer;
+use core::drawing::cell_buffer_view::CellBufferView;
+use core::drawing::point_2d::Point2d;
+use core::drawing::rect::Rect;
+use core::drawing::size_2d::Size2d;
+
+/// A view that draws in a rectangle of a cell buffer, so a widget can draw
+/// with its own coordinates without knowing where it is on the screen. The
+/// top left corner of the view is at the origin and every write is clipped
+/// by its rectangle. Drawing is done through the `Canvas` trait.
+#[derive(Debug)]
+pub struct CellBufferViewMut<'a> {
+    buffer: &'a mut CellBuffer,
+    origin: Point2d,
+    size: Size2d,
+    clip: Rect,
+}
+
+#[allow(dead_code)]
+impl<'a> CellBufferViewMut<'a> {
+    /// Creates a view of a rectangle of the buffer, which can be partially
+    /// or completely outside of it.
+    pub fn new(buffer: &'a mut CellBuffer, rect: Rect) -> CellBufferViewMut<'a> {
+        CellBufferViewMut::new_clipped(buffer, rect, rect)
+    }
+
+    /// Creates a view of a rectangle of the buffer that also clips the
+    /// writes outside of a clip rectangle, both in buffer coordinates.
+    pub fn new_clipped(
+        buffer: &'a mut CellBuffer,
+        rect: Rect,
+        clip: Rect,
+    ) -> CellBufferViewMut<'a> {
+        let clip = clip.intersect(rect).intersect(buffer.get_rect());
+
+        CellBufferViewMut {
+            buffer,
+            origin: rect.position,
+            size: rect.size,
+            clip,
+        }
+    }
+
+    /// Gets a read-only view of a rectangle of this view, in the coordinates
+    /// of this view.
+    pub fn get_view(&self, rect: Rect) -> CellBufferView<'_> {
+        CellBufferView::new_clipped(self.buffer, rect.offset(self.origin), self.clip)
+    }
+
+    /// Gets a view that draws in a rectangle of this view, in the
+    /// coordinates of this view. The nested view is clipped by this one.
+    pub fn get_view_mut(&mut self, rect: Rect) -> CellBufferViewMut<'_> {
+        let clip = self.clip;
+        CellBufferViewMut::new_clipped(self.buffer, rect.offset(self.origin), clip)
+    }
+
+    /// Gets a read-only view of the whole view.
+    pub fn as_view(&self) -> CellBufferView<'_> {
+        self.get_view(Rect::from_size(self.size))
+    }
+
+    /// Gets the size of the view, including the clipped cells.
+    #[inline]
+    pub fn get_size(&self) -> Size2d {
+        self.size
+    }
+
+    /// Gets the position of the top left corner of the view in the buffer.
+    #[inline]
+    pub fn get_origin(&self) -> Point2d {
+        self.origin
+    }
+
+    /// Gets a cell. Panics when the cell is clipped.
+    #[inline]
+    pub fn get(&self, position: Point2d) -> Cell {
+        match self.get_cell(position) {
+            Some(cell) => cell,
+            None => panic!("The position {:?} is outside of the view.", position),
+        }
+    }
+
+    /// Sets a cell, ignoring the clipped positions. Like in the buffer,
+    /// overwriting half of a wide character blanks the other half, even
+    /// when it is clipped.
+    #[inline]
+    pub fn set(&mut self, position: Point2d, cell: Cell) {
+        let position = position + self.origin;
+
+        if self.clip.contains(position) {
+            self.buffer.set(position, cell);
+        }
+    }
+}
+
+impl<'a> Canvas for CellBufferViewMut<'a> {
+    #[inline]
+    fn get_rect(&self) -> Rect {
+        self.clip.offset(-self.origin)
+    }
+
+    #[inline]
+    fn get_cell(&self, position: Point2d) -> Option<Cell> {
+        let position = position + self.origin;
+
+        if self.clip.contains(position) {
+            Some(self.buffer.get(position))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn set_cell(&mut self, position: Point2d, cell: Cell) {
+        self.set(position, cell);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::canvas::Canvas;
+    use core::drawing::cell::Cell;
+    use core::drawing::cell_buffer::CellBuffer;
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::rect::Rect;
+    use core::drawing::size_2d::Size2d;
+
+    fn create_buffer() -> CellBuffer {
+        CellBuffer::new(Cell::new_default('.'), Size2d::new(6, 4))
+    }
+
+    fn get_rows(buffer: &CellBuffer) -> Vec<String> {
+        (0..buffer.size.height as i32)
+            .map(|y| {
+                (0..buffer.size.width as i32)
+                    .map(|x| buffer.get(Point2d::new(x, y)).character)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nested_views_add_their_offsets() {
+        let mut buffer = create_buffer();
+        {
+            let mut view = buffer.get_view_mut(Rect::new(Point2d::new(1, 1), Size2d::new(4, 3)));
+            let mut nested = view.get_view_mut(Rect::new(Point2d::new(2, 1), Size2d::new(2, 2)));
+
+            assert_eq!(nested.get_origin(), Point2d::new(3, 2));
+            nested.set(Point2d::new(0, 0), Cell::new_default('#'));
+            nested.set(Point2d::new(1, 1), Cell::new_default('#'));
+        }
+
+        assert_eq!(
+            get_rows(&buffer),
+            vec!["......", "......", "...#..", "....#."]
+        );
+    }
+
+    #[test]
+    fn writes_outside_of_the_view_are_clipped() {
+        let mut buffer = create_buffer();
+        {
+            let mut view = buffer.get_view_mut(Rect::new(Point2d::new(1, 1), Size2d::new(2, 2)));
+
+            view.fill_rect(
+                Rect::new(Point2d::new(-3, -3), Size2d::new(10, 10)),
+                Cell::new_default('#'),
+            );
+            view.set(Point2d::new(2, 0), Cell::new_default('!'));
+            view.set(Point2d::new(-1, 0), Cell::new_default('!'));
+            assert!(view.get_cell(Point2d::new(2, 0)).is_none());
+        }
+
+        assert_eq!(
+            get_rows(&buffer),
+            vec!["......", ".##...", ".##...", "......"]
+        );
+    }
+
+    #[test]
+    fn nested_views_are_clipped_by_their_parent() {
+        let mut buffer = create_buffer();
+        {
+            let mut view = buffer.get_view_mut(Rect::new(Point2d::new(1, 0), Size2d::new(3, 2)));
+            let mut nested = view.get_view_mut(Rect::new(Point2d::new(1, 1), Size2d::new(4, 4)));
+
+            assert_eq!(nested.get_rect(), Rect::from_size(Size2d::new(2, 1)));
+            nested.fill_rect(Rect::from_size(Size2d::new(4, 4)), Cell::new_default('#'));
+        }
+
+        assert_eq!(
+            get_rows(&buffer),
+            vec!["......", "..##..", "......", "......"]
+        );
+    }
+
+    #[test]
+    fn negative_origin_hides_the_cells_before_the_buffer() {
+        let mut buffer = create_buffer();
+        {
+            let mut view = buffer.get_view_mut(Rect::new(Point2d::new(-2, -1), Size2d::new(4, 3)));
+
+            assert_eq!(
+                view.get_rect(),
+                Rect::new(Point2d::new(2, 1), Size2d::new(2, 2))
+            );
+            view.set(Point2d::new(0, 0), Cell::new_default('!'));
+            view.set(Point2d::new(1, 1), Cell::new_default('!'));
+            view.set(Point2d::new(2, 1), Cell::new_default('#'));
+            view.set(Point2d::new(3, 2), Cell::new_default('#'));
+        }
+
+        assert_eq!(
+            get_rows(&buffer),
+            vec!["#.....", ".#....", "......", "......"]
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use std::cmp::Ordering;
+
+/// Ranges of characters that take no columns: combining marks, which attach
+/// to the previous character, and zero width format characters.
+const ZERO_WIDTH_RANGES: &[(u32, u32)] = &[
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x05BF, 0x05BF),
+    (0x05C1, 0x05C2),
+    (0x05C4, 0x05C5),
+    (0x05C7, 0x05C7),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0670, 0x0670),
+    (0x06D6, 0x06DC),
+    (0x06DF, 0x06E4),
+    (0x06E7, 0x06E8),
+    (0x06EA, 0x06ED),
+    (0x0711, 0x0711),
+    (0x0730, 0x074A),
+    (0x07A6, 0x07B0),
+    (0x0900, 0x0902),
+    (0x093A, 0x093A),
+    (0x093C, 0x093C),
+    (0x0941, 0x0948),
+    (0x094D, 0x094D),
+    (0x0951, 0x0957),
+    (0x0962, 0x0963),
+    (0x0981, 0x0981),
+    (0x09BC, 0x09BC),
+    (0x09C1, 0x09C4),
+    (0x09CD, 0x09CD),
+    (0x0A01, 0x0A02),
+    (0x0A3C, 0x0A3C),
+    (0x0A41, 0x0A51),
+    (0x0A81, 0x0A82),
+    (0x0ABC, 0x0ABC),
+    (0x0AC1, 0x0AC8),
+    (0x0ACD, 0x0ACD),
+    (0x0B01, 0x0B01),
+    (0x0B3C, 0x0B3C),
+    (0x0B41, 0x0B44),
+    (0x0B4D, 0x0B4D),
+    (0x0BC0, 0x0BC0),
+    (0x0BCD, 0x0BCD),
+    (0x0C3E, 0x0C40),
+    (0x0C46, 0x0C56),
+    (0x0CBC, 0x0CBC),
+    (0x0CCC, 0x0CCD),
+    (0x0D41, 0x0D44),
+    (0x0D4D, 0x0D4D),
+    (0x0DCA, 0x0DCA),
+    (0x0DD2, 0x0DD6),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x0EB1, 0x0EB1),
+    (0x0EB4, 0x0EBC),
+    (0x0EC8, 0x0ECD),
+    (0x0F18, 0x0F19),
+    (0x0F35, 0x0F35),
+    (0x0F37, 0x0F37),
+    (0x0F39, 0x0F39),
+    (0x0F71, 0x0F7E),
+    (0x0F80, 0x0F84),
+    (0x0F86, 0x0F87),
+    (0x0F8D, 0x0FBC),
+    (0x102D, 0x1030),
+    (0x1032, 0x1037),
+    (0x1039, 0x103A),
+    (0x1160, 0x11FF),
+    (0x135D, 0x135F),
+    (0x1712, 0x1714),
+    (0x17B4, 0x17B5),
+    (0x17B7, 0x17BD),
+    (0x17C6, 0x17C6),
+    (0x17C9, 0x17D3),
+    (0x180B, 0x180F),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x202A, 0x202E),
+    (0x2060, 0x2064),
+    (0x20D0, 0x20FF),
+    (0x2CEF, 0x2CF1),
+    (0x2DE0, 0x2DFF),
+    (0x302A, 0x302D),
+    (0x3099, 0x309A),
+    (0xA66F, 0xA672),
+    (0xA674, 0xA67D),
+    (0xA69E, 0xA69F),
+    (0xA6F0, 0xA6F1),
+    (0xA8E0, 0xA8F1),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0xFEFF, 0xFEFF),
+    (0x1D167, 0x1D169),
+    (0x1D17B, 0x1D182),
+    (0x1F3FB, 0x1F3FF),
+    (0xE0001, 0xE007F),
+    (0xE0100, 0xE01EF),
+];
+
+/// Ranges of characters that take two columns: east asian wide and full
+/// width characters, and the emoji presented as pictures by default.
+const WIDE_RANGES: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x303E),
+    (0x3041, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x16FE0, 0x16FE4),
+    (0x17000, 0x18AFF),
+    (0x1B000, 0x1B2FF),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F200, 0x1F251),
+    (0x1F300, 0x1F320),
+    (0x1F32D, 0x1F335),
+    (0x1F337, 0x1F37C),
+    (0x1F37E, 0x1F393),
+    (0x1F3A0, 0x1F3CA),
+    (0x1F3CF, 0x1F3D3),
+    (0x1F3E0, 0x1F3F0),
+    (0x1F3F4, 0x1F3F4),
+    (0x1F3F8, 0x1F3FA),
+    (0x1F400, 0x1F43E),
+    (0x1F440, 0x1F440),
+    (0x1F442, 0x1F4FC),
+    (0x1F4FF, 0x1F53D),
+    (0x1F54B, 0x1F54E),
+    (0x1F550, 0x1F567),
+    (0x1F57A, 0x1F57A),
+    (0x1F595, 0x1F596),
+    (0x1F5A4, 0x1F5A4),
+    (0x1F5FB, 0x1F64F),
+    (0x1F680, 0x1F6C5),
+    (0x1F6CC, 0x1F6CC),
+    (0x1F6D0, 0x1F6D2),
+    (0x1F6D5, 0x1F6D7),
+    (0x1F6EB, 0x1F6EC),
+    (0x1F6F4, 0x1F6FC),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F90C, 0x1F93A),
+    (0x1F93C, 0x1F945),
+    (0x1F947, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+];
+
+/// Gets the amount of columns a character takes on a terminal: 0 for
+/// combining and zero width characters, 2 for wide characters and 1 for the
+/// rest, including the control characters.
+pub fn get_char_width(character: char) -> usize {
+    let code = character as u32;
+
+    if code < 0x0300 {
+        return 1;
+    }
+
+    if is_in_ranges(code, ZERO_WIDTH_RANGES) {
+        0
+    } else if is_in_ranges(code, WIDE_RANGES) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Binary searches a sorted list of inclusive ranges.
+#[inline]
+fn is_in_ranges(code: u32, ranges: &[(u32, u32)]) -> bool {
+    ranges
+        .binary_search_by(|&(start, end)| {
+            if end < code {
+                Ordering::Less
+            } else if start > code {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::char_width::{get_char_width, WIDE_RANGES, ZERO_WIDTH_RANGES};
+
+    #[test]
+    fn ascii_and_latin_take_one_column() {
+        assert_eq!(get_char_width('a'), 1);
+        assert_eq!(get_char_width('~'), 1);
+        assert_eq!(get_char_width('é'), 1);
+        assert_eq!(get_char_width('░'), 1);
+    }
+
+    #[test]
+    fn control_characters_take_one_column() {
+        assert_eq!(get_char_width('\0'), 1);
+        assert_eq!(get_char_width('\t'), 1);
+        assert_eq!(get_char_width('\u{7F}'), 1);
+    }
+
+    #[test]
+    fn cjk_and_emoji_take_two_columns() {
+        assert_eq!(get_char_width('界'), 2);
+        assert_eq!(get_char_width('한'), 2);
+        assert_eq!(get_char_width('カ'), 2);
+        assert_eq!(get_char_width('Ａ'), 2);
+        assert_eq!(get_char_width('😀'), 2);
+        assert_eq!(get_char_width('🚀'), 2);
+    }
+
+    #[test]
+    fn combining_and_zero_width_characters_take_no_columns() {
+        assert_eq!(get_char_width('\u{0301}'), 0);
+        assert_eq!(get_char_width('\u{20DD}'), 0);
+        assert_eq!(get_char_width('\u{200B}'), 0);
+        assert_eq!(get_char_width('\u{FE0F}'), 0);
+        assert_eq!(get_char_width('\u{1F3FB}'), 0);
+    }
+
+    #[test]
+    fn range_bounds_are_inclusive() {
+        assert_eq!(get_char_width('\u{4E00}'), 2);
+        assert_eq!(get_char_width('\u{9FFF}'), 2);
+        assert_eq!(get_char_width('\u{A4D0}'), 1);
+        assert_eq!(get_char_width('\u{036F}'), 0);
+        assert_eq!(get_char_width('\u{0370}'), 1);
+    }
+
+    #[test]
+    fn ranges_are_sorted_for_the_binary_search() {
+        for ranges in [ZERO_WIDTH_RANGES, WIDE_RANGES].iter() {
+            for pair in ranges.windows(2) {
+                assert!(pair[0].0 <= pair[0].1);
+                assert!(pair[0].1 < pair[1].0, "{:X?} overlaps", pair);
+            }
+        }
+    }
+}
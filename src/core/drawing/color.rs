@@ -1,7 +1,11 @@
+use core::drawing::color_depth::ColorDepth;
+
+/// Intensity of the six levels of each channel in the 256 color cube.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
 // TODO: remove allow dead code.
 #[allow(dead_code)]
 #[derive(Debug, Copy, Clone, PartialEq)]
-#[repr(u8)]
 pub enum Color {
     Black,
     Red,
@@ -19,4 +23,252 @@ pub enum Color {
     Grey,
     DarkGrey,
     White,
+    /// An entry of the xterm 256 color palette. The first 16 entries are the
+    /// console colors, followed by a 6x6x6 color cube and 24 grey levels.
+    Indexed(u8),
+    /// A 24 bit color.
+    Rgb(u8, u8, u8),
+}
+
+#[allow(dead_code)]
+impl Color {
+    /// Gets the console color of a given ANSI palette index (0 to 15).
+    pub fn from_ansi_index(index: u8) -> Color {
+        match index & 0x0F {
+            0 => Color::Black,
+            1 => Color::DarkRed,
+            2 => Color::DarkGreen,
+            3 => Color::DarkYellow,
+            4 => Color::DarkBlue,
+            5 => Color::DarkMagenta,
+            6 => Color::DarkCyan,
+            7 => Color::Grey,
+            8 => Color::DarkGrey,
+            9 => Color::Red,
+            10 => Color::Green,
+            11 => Color::Yellow,
+            12 => Color::Blue,
+            13 => Color::Magenta,
+            14 => Color::Cyan,
+            _ => Color::White,
+        }
+    }
+
+    /// Gets the ANSI palette index (0 to 15) of a console color, or `None`
+    /// for indexed and RGB colors outside of the 16 console colors.
+    pub fn get_ansi_index(self) -> Option<u8> {
+        match self {
+            Color::Black => Some(0),
+            Color::DarkRed => Some(1),
+            Color::DarkGreen => Some(2),
+            Color::DarkYellow => Some(3),
+            Color::DarkBlue => Some(4),
+            Color::DarkMagenta => Some(5),
+            Color::DarkCyan => Some(6),
+            Color::Grey => Some(7),
+            Color::DarkGrey => Some(8),
+            Color::Red => Some(9),
+            Color::Green => Some(10),
+            Color::Yellow => Some(11),
+            Color::Blue => Some(12),
+            Color::Magenta => Some(13),
+            Color::Cyan => Some(14),
+            Color::White => Some(15),
+            Color::Indexed(index) if index < 16 => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Gets the RGB components of the color. Console colors use the classic
+    /// VGA palette, which is also the default one of the windows console.
+    pub fn to_rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Black => (0, 0, 0),
+            Color::DarkRed => (128, 0, 0),
+            Color::DarkGreen => (0, 128, 0),
+            Color::DarkYellow => (128, 128, 0),
+            Color::DarkBlue => (0, 0, 128),
+            Color::DarkMagenta => (128, 0, 128),
+            Color::DarkCyan => (0, 128, 128),
+            Color::Grey => (192, 192, 192),
+            Color::DarkGrey => (128, 128, 128),
+            Color::Red => (255, 0, 0),
+            Color::Green => (0, 255, 0),
+            Color::Yellow => (255, 255, 0),
+            Color::Blue => (0, 0, 255),
+            Color::Magenta => (255, 0, 255),
+            Color::Cyan => (0, 255, 255),
+            Color::White => (255, 255, 255),
+            Color::Indexed(index) if index < 16 => Color::from_ansi_index(index).to_rgb(),
+            Color::Indexed(index) if index < 232 => {
+                let cube = index - 16;
+                (
+                    CUBE_LEVELS[(cube / 36) as usize],
+                    CUBE_LEVELS[(cube / 6 % 6) as usize],
+                    CUBE_LEVELS[(cube % 6) as usize],
+                )
+            }
+            Color::Indexed(index) => {
+                let level = 8 + 10 * (index - 232);
+                (level, level, level)
+            }
+            Color::Rgb(red, green, blue) => (red, green, blue),
+        }
+    }
+
+    /// Gets the nearest color that can be displayed with the given depth.
+    pub fn quantize(self, depth: ColorDepth) -> Color {
+        match (depth, self) {
+            (ColorDepth::TrueColor, _) => self,
+            (_, Color::Rgb(..)) | (ColorDepth::Ansi16, Color::Indexed(_)) => {
+                match self.get_ansi_index() {
+                    Some(index) => Color::from_ansi_index(index),
+                    None if depth == ColorDepth::Ansi16 => self.nearest_ansi(),
+                    None => self.nearest_indexed(),
+                }
+            }
+            _ => self,
+        }
+    }
+
+    /// Gets the nearest of the 16 console colors.
+    fn nearest_ansi(self) -> Color {
+        let rgb = self.to_rgb();
+
+        (0..16)
+            .map(Color::from_ansi_index)
+            .min_by_key(|color| get_distance(rgb, color.to_rgb()))
+            .unwrap_or(Color::Black)
+    }
+
+    /// Gets the nearest entry of the color cube or the grey ramp of the 256
+    /// color palette. The first 16 entries are skipped, since terminals let
+    /// the users change them.
+    fn nearest_indexed(self) -> Color {
+        let (red, green, blue) = self.to_rgb();
+        let cube = 16 + 36 * get_cube_level(red) + 6 * get_cube_level(green) + get_cube_level(blue);
+
+        let average = (red as u32 + green as u32 + blue as u32) / 3;
+        let grey = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+
+        let cube = Color::Indexed(cube);
+        let grey = Color::Indexed(grey);
+
+        if get_distance((red, green, blue), grey.to_rgb())
+            < get_distance((red, green, blue), cube.to_rgb())
+        {
+            grey
+        } else {
+            cube
+        }
+    }
+}
+
+/// Gets the index of the nearest color cube level of a channel intensity.
+#[inline]
+fn get_cube_level(value: u8) -> u8 {
+    match value {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (value - 35) / 40,
+    }
+}
+
+/// Gets the weighted squared distance between two colors, giving more weight
+/// to the channels the eye is more sensitive to.
+#[inline]
+fn get_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let red = a.0 as i32 - b.0 as i32;
+    let green = a.1 as i32 - b.1 as i32;
+    let blue = a.2 as i32 - b.2 as i32;
+
+    (2 * red * red + 4 * green * green + 3 * blue * blue) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::color::{get_cube_level, Color};
+    use core::drawing::color_depth::ColorDepth;
+
+    #[test]
+    fn true_color_keeps_every_color() {
+        for color in [Color::Rgb(1, 2, 3), Color::Indexed(200), Color::DarkCyan].iter() {
+            assert_eq!(color.quantize(ColorDepth::TrueColor), *color);
+        }
+    }
+
+    #[test]
+    fn console_colors_are_kept_at_every_depth() {
+        for index in 0..16 {
+            let color = Color::from_ansi_index(index);
+            assert_eq!(color.quantize(ColorDepth::Indexed256), color);
+            assert_eq!(color.quantize(ColorDepth::Ansi16), color);
+        }
+    }
+
+    #[test]
+    fn exact_cube_colors_map_to_their_index() {
+        let depth = ColorDepth::Indexed256;
+
+        assert_eq!(Color::Rgb(0, 0, 0).quantize(depth), Color::Indexed(16));
+        assert_eq!(Color::Rgb(255, 0, 0).quantize(depth), Color::Indexed(196));
+        assert_eq!(Color::Rgb(95, 135, 175).quantize(depth), Color::Indexed(67));
+        assert_eq!(
+            Color::Rgb(255, 255, 255).quantize(depth),
+            Color::Indexed(231)
+        );
+
+        for index in 16..232 {
+            let (red, green, blue) = Color::Indexed(index).to_rgb();
+            assert_eq!(
+                Color::Rgb(red, green, blue).quantize(depth),
+                Color::Indexed(index)
+            );
+        }
+    }
+
+    #[test]
+    fn greys_map_to_the_grey_ramp() {
+        let depth = ColorDepth::Indexed256;
+
+        assert_eq!(Color::Rgb(8, 8, 8).quantize(depth), Color::Indexed(232));
+        assert_eq!(
+            Color::Rgb(128, 128, 128).quantize(depth),
+            Color::Indexed(244)
+        );
+        assert_eq!(
+            Color::Rgb(238, 238, 238).quantize(depth),
+            Color::Indexed(255)
+        );
+        assert_eq!(
+            Color::Rgb(130, 127, 129).quantize(depth),
+            Color::Indexed(244)
+        );
+    }
+
+    #[test]
+    fn cube_levels_split_at_the_midpoints() {
+        assert_eq!(get_cube_level(47), 0);
+        assert_eq!(get_cube_level(48), 1);
+        assert_eq!(get_cube_level(114), 1);
+        assert_eq!(get_cube_level(115), 2);
+        assert_eq!(get_cube_level(154), 2);
+        assert_eq!(get_cube_level(155), 3);
+        assert_eq!(get_cube_level(234), 4);
+        assert_eq!(get_cube_level(235), 5);
+        assert_eq!(get_cube_level(255), 5);
+    }
+
+    #[test]
+    fn ansi_depth_picks_the_nearest_console_color() {
+        let depth = ColorDepth::Ansi16;
+
+        assert_eq!(Color::Rgb(250, 10, 10).quantize(depth), Color::Red);
+        assert_eq!(Color::Rgb(0, 0, 120).quantize(depth), Color::DarkBlue);
+        assert_eq!(Color::Rgb(128, 128, 128).quantize(depth), Color::DarkGrey);
+        assert_eq!(Color::Rgb(200, 200, 190).quantize(depth), Color::Grey);
+        assert_eq!(Color::Indexed(196).quantize(depth), Color::Red);
+        assert_eq!(Color::Indexed(9).quantize(depth), Color::Red);
+        assert_eq!(Color::Indexed(232).quantize(depth), Color::Black);
+    }
 }
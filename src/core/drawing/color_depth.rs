@@ -0,0 +1,10 @@
+/// Enumerates the amount of colors a terminal is able to display.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub enum ColorDepth {
+    /// The 16 console colors.
+    Ansi16,
+    /// The xterm 256 color palette.
+    Indexed256,
+    /// Any 24 bit RGB color.
+    TrueColor,
+}
@@ -0,0 +1,10 @@
+/// Enumerates the neighbours a flood fill spreads to.
+#[allow(dead_code)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Connectivity {
+    /// The cells above, below, to the left and to the right.
+    Four,
+
+    /// The four cells of `Four` and the diagonal ones.
+    Eight,
+}
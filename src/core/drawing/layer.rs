@@ -0,0 +1,190 @@
+use core::drawing::canvas::Canvas;
+use core::drawing::cell::Cell;
+use core::drawing::cell_blend::CellBlend;
+use core::drawing::cell_buffer::CellBuffer;
+use core::drawing::point_2d::Point2d;
+use core::drawing::rect::Rect;
+use core::drawing::size_2d::Size2d;
+
+/// A cell buffer with transparent cells, composed over the layers below it
+/// in a `LayerStack`. Every drawn cell keeps the blend that was current when
+/// it was drawn, and the cells that were never drawn or were cleared are
+/// transparent.
+#[derive(Debug, Clone)]
+pub struct Layer {
+    buffer: CellBuffer,
+    blends: Vec<Option<CellBlend>>,
+    blend: CellBlend,
+    z_order: i32,
+    visible: bool,
+}
+
+#[allow(dead_code)]
+impl Layer {
+    pub fn new(size: Size2d, z_order: i32) -> Layer {
+        Layer {
+            buffer: CellBuffer::new(Cell::new_default(' '), size),
+            blends: vec![None; size.width * size.height],
+            blend: CellBlend::opaque(),
+            z_order,
+            visible: true,
+        }
+    }
+
+    #[inline]
+    pub fn get_size(&self) -> Size2d {
+        self.buffer.size
+    }
+
+    /// Resizes the layer, making every cell transparent.
+    pub fn resize(&mut self, size: Size2d) {
+        self.buffer.resize(Cell::new_default(' '), size);
+        self.blends = vec![None; size.width * size.height];
+    }
+
+    /// Gets the cells of the layer, including the transparent ones.
+    #[inline]
+    pub fn get_buffer(&self) -> &CellBuffer {
+        &self.buffer
+    }
+
+    /// Layers with a higher z-order are composed over the lower ones.
+    #[inline]
+    pub fn get_z_order(&self) -> i32 {
+        self.z_order
+    }
+
+    pub fn set_z_order(&mut self, z_order: i32) {
+        self.z_order = z_order;
+    }
+
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Shows or hides the layer, keeping its cells.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// Gets the blend given to the cells drawn from now on.
+    #[inline]
+    pub fn get_blend(&self) -> CellBlend {
+        self.blend
+    }
+
+    /// Sets the blend given to the cells drawn from now on.
+    pub fn set_blend(&mut self, blend: CellBlend) {
+        self.blend = blend;
+    }
+
+    /// Gets the blend of a cell, or none when it is transparent or outside
+    /// of the layer.
+    pub fn get_cell_blend(&self, position: Point2d) -> Option<CellBlend> {
+        self.buffer
+            .index_of(position)
+            .and_then(|index| self.blends[index])
+    }
+
+    /// Makes every cell transparent and blank, like in a new layer.
+    pub fn clear(&mut self) {
+        for cell in self.buffer.iter_mut() {
+            *cell = Cell::new_default(' ');
+        }
+
+        for blend in self.blends.iter_mut() {
+            *blend = None;
+        }
+    }
+
+    /// Makes the cells of a rectangle transparent and blank.
+    pub fn clear_rect(&mut self, rect: Rect) {
+        for position in rect.intersect(self.buffer.get_rect()).iter_cells() {
+            if let Some(index) = self.buffer.index_of(position) {
+                self.buffer.set(position, Cell::new_default(' '));
+                self.blends[index] = None;
+            }
+        }
+    }
+
+    /// Blends the cells that aren't transparent over a buffer, clipped by it.
+    pub fn compose_onto(&self, target: &mut CellBuffer) {
+        if !self.visible {
+            return;
+        }
+
+        for (index, blend) in self.blends.iter().enumerate() {
+            let blend = match blend {
+                Some(blend) => blend,
+                None => continue,
+            };
+
+            let position = self.buffer.coordinates_of(index);
+
+            if let Some(below) = target.get_cell(position) {
+                target.set(position, blend.apply(below, self.buffer.get(position)));
+            }
+        }
+    }
+}
+
+impl Canvas for Layer {
+    #[inline]
+    fn get_rect(&self) -> Rect {
+        self.buffer.get_rect()
+    }
+
+    #[inline]
+    fn get_cell(&self, position: Point2d) -> Option<Cell> {
+        self.buffer.get_cell(position)
+    }
+
+    #[inline]
+    fn set_cell(&mut self, position: Point2d, cell: Cell) {
+        if let Some(index) = self.buffer.index_of(position) {
+            self.buffer.set(position, cell);
+            self.blends[index] = Some(self.blend);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::canvas::Canvas;
+    use core::drawing::cell::Cell;
+    use core::drawing::layer::Layer;
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::rect::Rect;
+    use core::drawing::size_2d::Size2d;
+
+    #[test]
+    fn clear_resets_the_cells_to_blank() {
+        let mut layer = Layer::new(Size2d::new(3, 2), 0);
+        layer.fill_rect(Rect::from_size(Size2d::new(3, 2)), Cell::new_default('#'));
+        layer.clear();
+
+        for position in Rect::from_size(Size2d::new(3, 2)).iter_cells() {
+            assert_eq!(layer.get_cell(position), Some(Cell::new_default(' ')));
+            assert!(layer.get_cell_blend(position).is_none());
+        }
+    }
+
+    #[test]
+    fn clear_rect_only_resets_its_cells() {
+        let mut layer = Layer::new(Size2d::new(3, 2), 0);
+        layer.fill_rect(Rect::from_size(Size2d::new(3, 2)), Cell::new_default('#'));
+        layer.clear_rect(Rect::new(Point2d::new(1, 1), Size2d::new(5, 5)));
+
+        assert_eq!(
+            layer.get_cell(Point2d::new(2, 1)),
+            Some(Cell::new_default(' '))
+        );
+        assert!(layer.get_cell_blend(Point2d::new(2, 1)).is_none());
+        assert_eq!(
+            layer.get_cell(Point2d::new(2, 0)),
+            Some(Cell::new_default('#'))
+        );
+        assert!(layer.get_cell_blend(Point2d::new(2, 0)).is_some());
+    }
+}
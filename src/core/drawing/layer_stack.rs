@@ -0,0 +1,141 @@
+use core::drawing::canvas::Canvas;
+use core::drawing::cell::Cell;
+use core::drawing::cell_buffer::CellBuffer;
+use core::drawing::layer::Layer;
+use core::drawing::point_2d::Point2d;
+use core::drawing::size_2d::Size2d;
+
+/// The layers of a frame, like the map, the entities, the effects and the
+/// user interface, composed into the buffer written to the terminal.
+#[derive(Debug, Clone)]
+pub struct LayerStack {
+    layers: Vec<Layer>,
+    frame: CellBuffer,
+}
+
+#[allow(dead_code)]
+impl LayerStack {
+    pub fn new() -> LayerStack {
+        LayerStack {
+            layers: Vec::new(),
+            frame: CellBuffer::new(Cell::new_default(' '), Size2d::empty()),
+        }
+    }
+
+    /// Adds a layer and gets its index.
+    pub fn add_layer(&mut self, layer: Layer) -> usize {
+        self.layers.push(layer);
+        self.layers.len() - 1
+    }
+
+    /// Removes a layer. The indices of the layers added after it go down by
+    /// one.
+    pub fn remove_layer(&mut self, index: usize) -> Layer {
+        self.layers.remove(index)
+    }
+
+    pub fn get_layer(&self, index: usize) -> Option<&Layer> {
+        self.layers.get(index)
+    }
+
+    pub fn get_mut_layer(&mut self, index: usize) -> Option<&mut Layer> {
+        self.layers.get_mut(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Composes the visible layers over a buffer from the lowest z-order to
+    /// the highest one. Layers with the same z-order are composed in the
+    /// order they were added.
+    pub fn compose_onto(&self, target: &mut CellBuffer) {
+        compose_layers(&self.layers, target);
+    }
+
+    /// Composes the visible layers over a buffer filled with a background
+    /// cell, giving the frame to write to the terminal. The layers are
+    /// composed apart and only the cells that differ are copied, so the
+    /// dirty regions of the buffer keep the cells that really changed.
+    pub fn compose(&mut self, background: Cell, target: &mut CellBuffer) {
+        if self.frame.size.equal_to(target.size) {
+            let rect = self.frame.get_rect();
+            self.frame.fill_rect(rect, background);
+        } else {
+            self.frame.resize(background, target.size);
+        }
+
+        compose_layers(&self.layers, &mut self.frame);
+        target.write_cell_buffer(&self.frame, Point2d::empty());
+    }
+}
+
+impl Default for LayerStack {
+    fn default() -> LayerStack {
+        LayerStack::new()
+    }
+}
+
+/// Composes the visible layers over a buffer by z-order.
+fn compose_layers(layers: &[Layer], target: &mut CellBuffer) {
+    let mut visible: Vec<&Layer> = layers.iter().filter(|layer| layer.is_visible()).collect();
+    visible.sort_by_key(|layer| layer.get_z_order());
+
+    for layer in visible {
+        layer.compose_onto(target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::canvas::Canvas;
+    use core::drawing::cell::Cell;
+    use core::drawing::cell_buffer::CellBuffer;
+    use core::drawing::layer::Layer;
+    use core::drawing::layer_stack::LayerStack;
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::row_span::RowSpan;
+    use core::drawing::size_2d::Size2d;
+
+    #[test]
+    fn composes_by_z_order() {
+        let size = Size2d::new(4, 1);
+        let mut stack = LayerStack::new();
+        let mut top = Layer::new(size, 1);
+        top.set_cell(Point2d::new(1, 0), Cell::new_default('t'));
+        let mut bottom = Layer::new(size, 0);
+        bottom.fill_rect(bottom.get_rect(), Cell::new_default('b'));
+        stack.add_layer(top);
+        stack.add_layer(bottom);
+
+        let mut frame = CellBuffer::new(Cell::new_default(' '), size);
+        stack.compose(Cell::new_default('.'), &mut frame);
+        let text: String = frame.iter().map(|cell| cell.character).collect();
+        assert_eq!(text, "btbb");
+    }
+
+    #[test]
+    fn compose_only_dirties_the_changed_cells() {
+        let size = Size2d::new(6, 3);
+        let mut stack = LayerStack::new();
+        let mut layer = Layer::new(size, 0);
+        layer.fill_rect(layer.get_rect(), Cell::new_default('#'));
+        let index = stack.add_layer(layer);
+
+        let mut frame = CellBuffer::new(Cell::new_default(' '), size);
+        stack.compose(Cell::new_default('.'), &mut frame);
+        frame.take_dirty_regions();
+
+        stack.compose(Cell::new_default('.'), &mut frame);
+        assert!(frame.take_dirty_regions().is_empty());
+
+        let layer = stack.get_mut_layer(index).unwrap();
+        layer.set_cell(Point2d::new(2, 1), Cell::new_default('@'));
+        stack.compose(Cell::new_default('.'), &mut frame);
+        assert_eq!(frame.take_dirty_regions(), vec![RowSpan::new(1, 2, 3)]);
+    }
+}
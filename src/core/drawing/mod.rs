@@ -1,5 +1,22 @@
+pub mod blend_mode;
+pub mod box_drawing;
+pub mod box_style;
+pub mod canvas;
 pub mod cell;
+pub mod cell_blend;
 pub mod cell_buffer;
+pub mod cell_buffer_view;
+pub mod cell_buffer_view_mut;
+pub mod char_width;
 pub mod color;
+pub mod color_depth;
+pub mod connectivity;
+pub mod layer;
+pub mod layer_stack;
 pub mod point_2d;
+pub mod rect;
+pub mod rect_cells;
+pub mod row_span;
 pub mod size_2d;
+pub mod style;
+pub mod sub_cell_point;
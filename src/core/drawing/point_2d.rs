@@ -1,12 +1,17 @@
-#[derive(Debug, Copy, Clone)]
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+/// A position in cells or pixels. The coordinates are signed, so positions
+/// to the left or above the origin, like a partially visible sprite, can be
+/// represented.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Point2d {
-    pub x: usize,
-    pub y: usize,
+    pub x: i32,
+    pub y: i32,
 }
 
 #[allow(dead_code)]
 impl Point2d {
-    pub fn new(x: usize, y: usize) -> Point2d {
+    pub fn new(x: i32, y: i32) -> Point2d {
         Point2d { x, y }
     }
 
@@ -18,14 +23,18 @@ impl Point2d {
         Point2d::new(self.x + point.x, self.y + point.y)
     }
 
-    pub fn add_x(&self, x: usize) -> Point2d {
+    pub fn add_x(&self, x: i32) -> Point2d {
         Point2d::new(self.x + x, self.y)
     }
 
-    pub fn add_y(&self, y: usize) -> Point2d {
+    pub fn add_y(&self, y: i32) -> Point2d {
         Point2d::new(self.x, self.y + y)
     }
 
+    pub fn sub(&self, point: Point2d) -> Point2d {
+        Point2d::new(self.x - point.x, self.y - point.y)
+    }
+
     pub fn equal_to(&self, point: Point2d) -> bool {
         self.x == point.x && self.y == point.y
     }
@@ -34,3 +43,39 @@ impl Point2d {
         self.x == 0 && self.y == 0
     }
 }
+
+impl Add for Point2d {
+    type Output = Point2d;
+
+    fn add(self, point: Point2d) -> Point2d {
+        Point2d::new(self.x + point.x, self.y + point.y)
+    }
+}
+
+impl Sub for Point2d {
+    type Output = Point2d;
+
+    fn sub(self, point: Point2d) -> Point2d {
+        Point2d::new(self.x - point.x, self.y - point.y)
+    }
+}
+
+impl Neg for Point2d {
+    type Output = Point2d;
+
+    fn neg(self) -> Point2d {
+        Point2d::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Point2d {
+    fn add_assign(&mut self, point: Point2d) {
+        *self = *self + point;
+    }
+}
+
+impl SubAssign for Point2d {
+    fn sub_assign(&mut self, point: Point2d) {
+        *self = *self - point;
+    }
+}
@@ -0,0 +1,246 @@
+use core::drawing::point_2d::Point2d;
+use core::drawing::rect_cells::RectCells;
+use core::drawing::size_2d::Size2d;
+
+/// A rectangle of cells or pixels, from its top left corner. The right and
+/// bottom edges are exclusive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rect {
+    pub position: Point2d,
+    pub size: Size2d,
+}
+
+#[allow(dead_code)]
+impl Rect {
+    pub fn new(position: Point2d, size: Size2d) -> Rect {
+        Rect { position, size }
+    }
+
+    /// Creates a rectangle at the origin.
+    pub fn from_size(size: Size2d) -> Rect {
+        Rect::new(Point2d::empty(), size)
+    }
+
+    /// Creates the smallest rectangle that contains both corners.
+    pub fn from_corners(start: Point2d, end: Point2d) -> Rect {
+        let left = start.x.min(end.x);
+        let top = start.y.min(end.y);
+
+        Rect::new(
+            Point2d::new(left, top),
+            Size2d::new(
+                (start.x.max(end.x) - left) as usize + 1,
+                (start.y.max(end.y) - top) as usize + 1,
+            ),
+        )
+    }
+
+    pub fn empty() -> Rect {
+        Rect::new(Point2d::empty(), Size2d::empty())
+    }
+
+    #[inline]
+    pub fn get_left(&self) -> i32 {
+        self.position.x
+    }
+
+    #[inline]
+    pub fn get_top(&self) -> i32 {
+        self.position.y
+    }
+
+    /// Gets the column after the last one of the rectangle.
+    #[inline]
+    pub fn get_right(&self) -> i32 {
+        self.position.x + self.size.width as i32
+    }
+
+    /// Gets the row after the last one of the rectangle.
+    #[inline]
+    pub fn get_bottom(&self) -> i32 {
+        self.position.y + self.size.height as i32
+    }
+
+    /// Checks if the rectangle has no cells.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size.width == 0 || self.size.height == 0
+    }
+
+    pub fn contains(&self, point: Point2d) -> bool {
+        point.x >= self.get_left()
+            && point.x < self.get_right()
+            && point.y >= self.get_top()
+            && point.y < self.get_bottom()
+    }
+
+    pub fn contains_rect(&self, rect: Rect) -> bool {
+        rect.is_empty()
+            || (rect.get_left() >= self.get_left()
+                && rect.get_right() <= self.get_right()
+                && rect.get_top() >= self.get_top()
+                && rect.get_bottom() <= self.get_bottom())
+    }
+
+    pub fn intersects(&self, rect: Rect) -> bool {
+        !self.intersect(rect).is_empty()
+    }
+
+    /// Gets the cells shared by both rectangles, an empty rectangle when they
+    /// don't overlap.
+    pub fn intersect(&self, rect: Rect) -> Rect {
+        let left = self.get_left().max(rect.get_left());
+        let top = self.get_top().max(rect.get_top());
+        let right = self.get_right().min(rect.get_right());
+        let bottom = self.get_bottom().min(rect.get_bottom());
+
+        if right <= left || bottom <= top {
+            return Rect::new(Point2d::new(left, top), Size2d::empty());
+        }
+
+        Rect::new(
+            Point2d::new(left, top),
+            Size2d::new((right - left) as usize, (bottom - top) as usize),
+        )
+    }
+
+    /// Gets the smallest rectangle that contains both rectangles. Empty
+    /// rectangles are ignored.
+    pub fn union(&self, rect: Rect) -> Rect {
+        if rect.is_empty() {
+            return *self;
+        }
+
+        if self.is_empty() {
+            return rect;
+        }
+
+        let left = self.get_left().min(rect.get_left());
+        let top = self.get_top().min(rect.get_top());
+        let right = self.get_right().max(rect.get_right());
+        let bottom = self.get_bottom().max(rect.get_bottom());
+
+        Rect::new(
+            Point2d::new(left, top),
+            Size2d::new((right - left) as usize, (bottom - top) as usize),
+        )
+    }
+
+    /// Grows the rectangle by the given amount of cells on every side, or
+    /// shrinks it with negative amounts.
+    pub fn inflate(&self, horizontal: i32, vertical: i32) -> Rect {
+        let width = (self.size.width as i32 + horizontal * 2).max(0);
+        let height = (self.size.height as i32 + vertical * 2).max(0);
+
+        Rect::new(
+            self.position - Point2d::new(horizontal, vertical),
+            Size2d::new(width as usize, height as usize),
+        )
+    }
+
+    /// Moves the rectangle by an offset.
+    pub fn offset(&self, offset: Point2d) -> Rect {
+        Rect::new(self.position + offset, self.size)
+    }
+
+    /// Iterates the cells of the rectangle, row by row.
+    pub fn iter_cells(&self) -> RectCells {
+        RectCells::new(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::rect::Rect;
+    use core::drawing::size_2d::Size2d;
+
+    #[test]
+    fn edges_are_exclusive() {
+        let rect = Rect::new(Point2d::new(2, 1), Size2d::new(3, 2));
+
+        assert_eq!(rect.get_right(), 5);
+        assert_eq!(rect.get_bottom(), 3);
+        assert!(rect.contains(Point2d::new(2, 1)));
+        assert!(rect.contains(Point2d::new(4, 2)));
+        assert!(!rect.contains(Point2d::new(5, 2)));
+        assert!(!rect.contains(Point2d::new(4, 3)));
+    }
+
+    #[test]
+    fn from_corners_includes_both_corners() {
+        let rect = Rect::from_corners(Point2d::new(5, 1), Point2d::new(2, 4));
+
+        assert_eq!(rect, Rect::new(Point2d::new(2, 1), Size2d::new(4, 4)));
+        assert_eq!(
+            Rect::from_corners(Point2d::new(3, 3), Point2d::new(3, 3)).size,
+            Size2d::new(1, 1)
+        );
+    }
+
+    #[test]
+    fn intersect_gets_the_shared_cells() {
+        let a = Rect::new(Point2d::new(0, 0), Size2d::new(4, 4));
+        let b = Rect::new(Point2d::new(2, 3), Size2d::new(4, 4));
+
+        assert_eq!(
+            a.intersect(b),
+            Rect::new(Point2d::new(2, 3), Size2d::new(2, 1))
+        );
+        assert!(a.intersects(b));
+
+        // rectangles that only touch share no cells.
+        let c = Rect::new(Point2d::new(4, 0), Size2d::new(2, 2));
+        assert!(a.intersect(c).is_empty());
+        assert!(!a.intersects(c));
+    }
+
+    #[test]
+    fn union_ignores_empty_rectangles() {
+        let a = Rect::new(Point2d::new(1, 1), Size2d::new(2, 2));
+        let b = Rect::new(Point2d::new(4, 0), Size2d::new(1, 5));
+
+        assert_eq!(a.union(b), Rect::new(Point2d::new(1, 0), Size2d::new(4, 5)));
+
+        let empty = Rect::new(Point2d::new(-10, -10), Size2d::empty());
+        assert_eq!(a.union(empty), a);
+        assert_eq!(empty.union(a), a);
+    }
+
+    #[test]
+    fn contains_rect_accepts_empty_rectangles() {
+        let rect = Rect::from_size(Size2d::new(4, 4));
+
+        assert!(rect.contains_rect(Rect::new(Point2d::new(1, 1), Size2d::new(3, 3))));
+        assert!(!rect.contains_rect(Rect::new(Point2d::new(1, 1), Size2d::new(4, 3))));
+        assert!(rect.contains_rect(Rect::new(Point2d::new(9, 9), Size2d::empty())));
+    }
+
+    #[test]
+    fn inflate_grows_and_shrinks_every_side() {
+        let rect = Rect::new(Point2d::new(2, 2), Size2d::new(3, 2));
+
+        assert_eq!(
+            rect.inflate(1, 2),
+            Rect::new(Point2d::new(1, 0), Size2d::new(5, 6))
+        );
+        assert!(rect.inflate(-2, 0).is_empty());
+    }
+
+    #[test]
+    fn iter_cells_goes_row_by_row() {
+        let rect = Rect::new(Point2d::new(1, 1), Size2d::new(2, 2));
+        let cells: Vec<Point2d> = rect.iter_cells().collect();
+
+        assert_eq!(
+            cells,
+            vec![
+                Point2d::new(1, 1),
+                Point2d::new(2, 1),
+                Point2d::new(1, 2),
+                Point2d::new(2, 2),
+            ]
+        );
+        assert_eq!(Rect::empty().iter_cells().count(), 0);
+    }
+}
@@ -0,0 +1,37 @@
+use core::drawing::point_2d::Point2d;
+use core::drawing::rect::Rect;
+
+/// Iterates the cells of a rectangle, row by row.
+#[derive(Debug, Clone)]
+pub struct RectCells {
+    rect: Rect,
+    next: Point2d,
+}
+
+impl RectCells {
+    pub fn new(rect: Rect) -> RectCells {
+        RectCells {
+            rect,
+            next: rect.position,
+        }
+    }
+}
+
+impl Iterator for RectCells {
+    type Item = Point2d;
+
+    fn next(&mut self) -> Option<Point2d> {
+        if self.rect.is_empty() || self.next.y >= self.rect.get_bottom() {
+            return None;
+        }
+
+        let cell = self.next;
+        self.next.x += 1;
+
+        if self.next.x >= self.rect.get_right() {
+            self.next = Point2d::new(self.rect.get_left(), self.next.y + 1);
+        }
+
+        Some(cell)
+    }
+}
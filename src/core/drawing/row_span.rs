@@ -0,0 +1,24 @@
+/// A horizontal run of cells inside a single buffer row.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RowSpan {
+    pub row: usize,
+    /// First column of the span.
+    pub start: usize,
+    /// Column after the last column of the span.
+    pub end: usize,
+}
+
+#[allow(dead_code)]
+impl RowSpan {
+    pub fn new(row: usize, start: usize, end: usize) -> RowSpan {
+        RowSpan { row, start, end }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+}
@@ -0,0 +1,73 @@
+use std::ops::{BitAnd, BitOr, BitOrAssign, Sub};
+
+/// Set of text attributes of a cell. Styles are combined with `|`, and the
+/// backends render the attributes they support, ignoring the rest.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Style(u8);
+
+#[allow(dead_code)]
+impl Style {
+    pub const NONE: Style = Style(0);
+    pub const BOLD: Style = Style(1);
+    pub const DIM: Style = Style(1 << 1);
+    pub const ITALIC: Style = Style(1 << 2);
+    pub const UNDERLINE: Style = Style(1 << 3);
+    pub const REVERSE: Style = Style(1 << 4);
+    pub const BLINK: Style = Style(1 << 5);
+    pub const STRIKETHROUGH: Style = Style(1 << 6);
+
+    pub fn from_bits(bits: u8) -> Style {
+        Style(bits & 0x7F)
+    }
+
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Checks whether every attribute of the given style is set.
+    pub fn contains(self, style: Style) -> bool {
+        self.0 & style.0 == style.0
+    }
+
+    pub fn insert(&mut self, style: Style) {
+        self.0 |= style.0;
+    }
+
+    pub fn remove(&mut self, style: Style) {
+        self.0 &= !style.0;
+    }
+}
+
+impl BitOr for Style {
+    type Output = Style;
+
+    fn bitor(self, style: Style) -> Style {
+        Style(self.0 | style.0)
+    }
+}
+
+impl BitOrAssign for Style {
+    fn bitor_assign(&mut self, style: Style) {
+        self.0 |= style.0;
+    }
+}
+
+impl BitAnd for Style {
+    type Output = Style;
+
+    fn bitand(self, style: Style) -> Style {
+        Style(self.0 & style.0)
+    }
+}
+
+impl Sub for Style {
+    type Output = Style;
+
+    fn sub(self, style: Style) -> Style {
+        Style(self.0 & !style.0)
+    }
+}
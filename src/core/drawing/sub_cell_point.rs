@@ -0,0 +1,82 @@
+use core::drawing::point_2d::Point2d;
+use core::drawing::size_2d::Size2d;
+
+/// A position in character cells with the fraction inside the cell, so
+/// `(2.5, 1.75)` is the middle of the bottom half of the cell `(2, 1)`.
+#[derive(Debug, Copy, Clone)]
+pub struct SubCellPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[allow(dead_code)]
+impl SubCellPoint {
+    pub fn new(x: f32, y: f32) -> SubCellPoint {
+        SubCellPoint { x, y }
+    }
+
+    /// Converts a position in pixels using the character size in pixels.
+    /// Gets none when the character size is unknown.
+    pub fn from_pixels(position: Point2d, char_size: Size2d) -> Option<SubCellPoint> {
+        if char_size.width == 0 || char_size.height == 0 {
+            return None;
+        }
+
+        Some(SubCellPoint::new(
+            position.x as f32 / char_size.width as f32,
+            position.y as f32 / char_size.height as f32,
+        ))
+    }
+
+    /// Gets the cell that contains the position.
+    pub fn get_cell(&self) -> Point2d {
+        Point2d::new(self.x.floor() as i32, self.y.floor() as i32)
+    }
+
+    /// Gets the position in a grid that splits every cell in the given
+    /// amount of columns and rows, like `(1, 2)` for half blocks or `(2, 4)`
+    /// for braille dots.
+    pub fn get_sub_cell(&self, columns: usize, rows: usize) -> Point2d {
+        SubCellPoint::new(self.x * columns as f32, self.y * rows as f32).get_cell()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::size_2d::Size2d;
+    use core::drawing::sub_cell_point::SubCellPoint;
+
+    #[test]
+    fn pixels_are_divided_by_the_char_size() {
+        let point = SubCellPoint::from_pixels(Point2d::new(20, 42), Size2d::new(8, 16)).unwrap();
+
+        assert_eq!(point.x, 2.5);
+        assert_eq!(point.y, 2.625);
+        assert_eq!(point.get_cell(), Point2d::new(2, 2));
+    }
+
+    #[test]
+    fn zero_char_size_is_unknown() {
+        let position = Point2d::new(20, 42);
+
+        assert!(SubCellPoint::from_pixels(position, Size2d::new(0, 16)).is_none());
+        assert!(SubCellPoint::from_pixels(position, Size2d::new(8, 0)).is_none());
+    }
+
+    #[test]
+    fn sub_cells_split_the_fraction() {
+        let point = SubCellPoint::new(2.5, 1.75);
+
+        assert_eq!(point.get_sub_cell(1, 2), Point2d::new(2, 3));
+        assert_eq!(point.get_sub_cell(2, 4), Point2d::new(5, 7));
+    }
+
+    #[test]
+    fn negative_positions_round_down() {
+        assert_eq!(
+            SubCellPoint::new(-0.25, -1.5).get_cell(),
+            Point2d::new(-1, -2)
+        );
+    }
+}
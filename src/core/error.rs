@@ -0,0 +1,89 @@
+use std::error;
+use std::fmt::{Display, Formatter, Result as FormatResult};
+use std::io;
+
+/// The failures of the terminal, window, mouse and application operations.
+#[derive(Debug)]
+pub enum Error {
+    /// The process has no console or terminal attached.
+    ConsoleUnavailable,
+
+    /// Reading from or writing to the console failed.
+    Io(io::Error),
+
+    /// A size or position doesn't fit in the range the platform accepts.
+    InvalidSize,
+
+    /// The backend doesn't support the operation.
+    Unsupported(&'static str),
+
+    /// The application that receives the sent events no longer exists.
+    Disconnected,
+
+    /// A configuration text has a malformed line, counted from 1.
+    InvalidConfig { line: usize, message: &'static str },
+
+    /// A platform call failed. The code is the one reported by the operating
+    /// system (`GetLastError` on Windows, `errno` on Unix).
+    BackendSpecific { code: i32, message: &'static str },
+}
+
+impl Error {
+    /// Creates a backend specific error with the last error code reported
+    /// by the operating system for the calling thread.
+    pub fn last_os_error(message: &'static str) -> Error {
+        Error::BackendSpecific {
+            code: io::Error::last_os_error().raw_os_error().unwrap_or(0),
+            message,
+        }
+    }
+
+    /// Gets the operating system error code, when there is one.
+    pub fn get_code(&self) -> Option<i32> {
+        match self {
+            Error::Io(error) => error.raw_os_error(),
+            Error::BackendSpecific { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Error::ConsoleUnavailable => {
+                write!(formatter, "No console is attached to the process.")
+            }
+            Error::Io(error) => write!(formatter, "Console input/output failed: {}", error),
+            Error::InvalidSize => write!(formatter, "The size or position is out of range."),
+            Error::Unsupported(message) => write!(formatter, "{}", message),
+            Error::Disconnected => {
+                write!(
+                    formatter,
+                    "The application that receives the events no longer exists."
+                )
+            }
+            Error::InvalidConfig { line, message } => {
+                write!(formatter, "{} (line {})", message, line)
+            }
+            Error::BackendSpecific { code, message } => {
+                write!(formatter, "{} (os error {})", message, code)
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Error {
+        Error::Io(error)
+    }
+}
@@ -0,0 +1,10 @@
+/// Enumerates what a full event queue does with a new event.
+#[allow(dead_code)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DropPolicy {
+    /// Drops the oldest queued event to make room for the new one.
+    DropOldest,
+
+    /// Drops the new event.
+    DropNewest,
+}
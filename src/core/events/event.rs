@@ -1,6 +1,8 @@
 use core::drawing::point_2d::Point2d;
 use core::drawing::size_2d::Size2d;
 use core::input::key::Key;
+use std::any::Any;
+use std::fmt;
 
 /// Enumerates all the possible mouse event types.
 #[allow(dead_code)]
@@ -9,8 +11,21 @@ pub enum MouseEventType {
     MouseMove,
     Click,
     DoubleClick,
-    HorizontalWheel,
-    VerticalWheel,
+
+    /// The wheel was turned. Positive lines scroll up or right, like a wheel
+    /// turned away from the user, on every backend.
+    Scroll {
+        axis: ScrollAxis,
+        lines: i32,
+    },
+}
+
+/// Enumerates the axes of a scroll wheel.
+#[allow(dead_code)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ScrollAxis {
+    Vertical,
+    Horizontal,
 }
 
 /// Enumerates all the possible keyboard event types.
@@ -22,7 +37,7 @@ pub enum KeyboardEventType {
 }
 
 /// Enumerates all the possible window event types.
-#[allow(dead_code)]
+#[allow(dead_code, clippy::enum_variant_names)]
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum WindowEventType {
     WindowMove,
@@ -45,7 +60,6 @@ pub struct MouseEvent {
     pub extra_button_3: bool,
     pub extra_button_4: bool,
     pub position: Point2d,
-    pub wheel_delta: i16,
 }
 
 /// Represents a keyboard event like key down or key up.
@@ -69,15 +83,45 @@ pub struct KeyboardEvent {
 #[derive(Debug, Copy, Clone)]
 pub struct WindowEvent {
     pub event_type: WindowEventType,
+
+    /// The window position in pixels, empty when the backend can't know it.
     pub position: Point2d,
+
+    /// The console size in character cells.
     pub size: Size2d,
 }
 
 /// Event object enumeration can be one of the valid event types.
 #[allow(dead_code)]
-#[derive(Debug, Copy, Clone)]
 pub enum Event {
     Mouse(MouseEvent),
     Keyboard(KeyboardEvent),
     Window(WindowEvent),
+
+    /// Text typed by the user, composed by the keyboard layout or the input
+    /// method. Key combinations with control or alt don't produce text.
+    Text(String),
+
+    /// Text pasted by the user at once, when the backend can tell it apart
+    /// from typed text.
+    Paste(String),
+
+    /// A custom event, usually sent from another thread with an
+    /// `EventSender`. Use `downcast_ref` to get the value.
+    User(Box<dyn Any + Send>),
+}
+
+impl fmt::Debug for Event {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Event::Mouse(ref mouse) => formatter.debug_tuple("Mouse").field(mouse).finish(),
+            Event::Keyboard(ref keyboard) => {
+                formatter.debug_tuple("Keyboard").field(keyboard).finish()
+            }
+            Event::Window(ref window) => formatter.debug_tuple("Window").field(window).finish(),
+            Event::Text(ref text) => formatter.debug_tuple("Text").field(text).finish(),
+            Event::Paste(ref text) => formatter.debug_tuple("Paste").field(text).finish(),
+            Event::User(_) => formatter.write_str("User(..)"),
+        }
+    }
 }
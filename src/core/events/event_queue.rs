@@ -1,26 +1,417 @@
-use core::events::event::Event;
+use core::events::drop_policy::DropPolicy;
+use core::events::event::{Event, MouseEventType, WindowEventType};
+use core::events::event_sender::EventSender;
+use core::events::timed_event::TimedEvent;
+use core::events::waker::Waker;
 use std::collections::vec_deque::VecDeque;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::Instant;
 
+/// The events of an application, in the order they happened, stamped with
+/// the time they were added. Other threads add events through the senders
+/// of the queue.
 #[allow(dead_code)]
 pub struct EventQueue {
-    queue: VecDeque<Event>,
+    queue: VecDeque<TimedEvent>,
     events: usize,
+    sent_events: usize,
+    dropped_events: usize,
+    max_len: usize,
+    capacity: Option<usize>,
+    drop_policy: DropPolicy,
+    coalescing: bool,
+    sender: Sender<Event>,
+    receiver: Receiver<Event>,
+    waker: Option<Arc<dyn Waker>>,
 }
 
 #[allow(dead_code)]
 impl EventQueue {
     pub fn new() -> EventQueue {
+        let (sender, receiver) = channel();
+
         EventQueue {
             queue: VecDeque::new(),
             events: 0,
+            sent_events: 0,
+            dropped_events: 0,
+            max_len: 0,
+            capacity: None,
+            drop_policy: DropPolicy::DropOldest,
+            coalescing: false,
+            sender,
+            receiver,
+            waker: None,
+        }
+    }
+
+    /// Sets what wakes up the application when an event is sent. Only
+    /// affects the senders created afterwards.
+    pub fn set_waker(&mut self, waker: Arc<dyn Waker>) {
+        self.waker = Some(waker);
+    }
+
+    /// Gets a sender that adds events to this queue from any thread.
+    pub fn get_sender(&self) -> EventSender {
+        EventSender::new(self.sender.clone(), self.waker.clone())
+    }
+
+    /// Sets the most events the queue holds, or none for no limit. The drop
+    /// policy decides which event is lost when the queue is full.
+    pub fn set_capacity(&mut self, capacity: Option<usize>) {
+        self.capacity = capacity.map(|capacity| capacity.max(1));
+
+        // the oldest events are lost, like with `DropPolicy::DropOldest`.
+        if let Some(capacity) = self.capacity {
+            while self.queue.len() > capacity {
+                self.queue.pop_front();
+                self.dropped_events += 1;
+            }
         }
     }
 
+    pub fn set_drop_policy(&mut self, drop_policy: DropPolicy) {
+        self.drop_policy = drop_policy;
+    }
+
+    /// Enables or disables the coalescing: a mouse move replaces a queued
+    /// mouse move with the same buttons right before it, and a window resize
+    /// replaces a queued resize right before it.
+    pub fn set_coalescing(&mut self, coalescing: bool) {
+        self.coalescing = coalescing;
+    }
+
+    /// Adds an event stamped with the current time.
     pub fn add_event(&mut self, event: Event) {
+        self.add_timed_event(TimedEvent::new(event, Instant::now()));
+    }
+
+    pub fn add_timed_event(&mut self, event: TimedEvent) {
+        self.events += 1;
+
+        if self.coalescing {
+            if let Some(last) = self.queue.back_mut() {
+                if can_coalesce(&last.event, &event.event) {
+                    *last = event;
+                    return;
+                }
+            }
+        }
+
+        if self.is_full() {
+            self.dropped_events += 1;
+
+            match self.drop_policy {
+                DropPolicy::DropOldest => self.queue.pop_front(),
+                DropPolicy::DropNewest => return,
+            };
+        }
+
         self.queue.push_back(event);
+        self.max_len = self.max_len.max(self.queue.len());
+    }
+
+    /// Adds the events sent since the previous call, in the order they were
+    /// sent. The applications call it when they listen the native events.
+    pub fn receive_sent_events(&mut self) {
+        while let Ok(event) = self.receiver.try_recv() {
+            self.sent_events += 1;
+            self.add_event(event);
+        }
+    }
+
+    /// Gets the amount of events added since the queue was created,
+    /// including the sent, coalesced and dropped ones.
+    #[inline]
+    pub fn get_total_events(&self) -> usize {
+        self.events
+    }
+
+    /// Gets the amount of events received from the senders.
+    #[inline]
+    pub fn get_sent_events(&self) -> usize {
+        self.sent_events
+    }
+
+    /// Gets the amount of events lost because the queue was full.
+    #[inline]
+    pub fn get_dropped_events(&self) -> usize {
+        self.dropped_events
+    }
+
+    /// Gets the largest amount of events the queue held at once.
+    #[inline]
+    pub fn get_max_len(&self) -> usize {
+        self.max_len
     }
 
     pub fn get_event(&mut self) -> Option<Event> {
+        self.queue.pop_front().map(|timed_event| timed_event.event)
+    }
+
+    /// Gets the next event with the time it was added.
+    pub fn get_timed_event(&mut self) -> Option<TimedEvent> {
         self.queue.pop_front()
     }
+
+    /// Gets the next event without removing it.
+    pub fn peek(&self) -> Option<&Event> {
+        self.queue.front().map(|timed_event| &timed_event.event)
+    }
+
+    /// Gets the first event that matches the predicate without removing it.
+    pub fn peek_where<P: FnMut(&Event) -> bool>(&self, mut predicate: P) -> Option<&Event> {
+        self.queue
+            .iter()
+            .map(|timed_event| &timed_event.event)
+            .find(|event| predicate(event))
+    }
+
+    /// Removes the events that match the predicate and gets them in order.
+    /// The other events stay in the queue.
+    pub fn drain_where<P: FnMut(&Event) -> bool>(&mut self, mut predicate: P) -> Vec<Event> {
+        let mut drained = Vec::new();
+        let mut kept = VecDeque::with_capacity(self.queue.len());
+
+        for timed_event in self.queue.drain(..) {
+            if predicate(&timed_event.event) {
+                drained.push(timed_event.event);
+            } else {
+                kept.push_back(timed_event);
+            }
+        }
+
+        self.queue = kept;
+        drained
+    }
+
+    /// Removes all the queued events.
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    #[inline]
+    fn is_full(&self) -> bool {
+        match self.capacity {
+            Some(capacity) => self.queue.len() >= capacity,
+            None => false,
+        }
+    }
+}
+
+impl Default for EventQueue {
+    fn default() -> EventQueue {
+        EventQueue::new()
+    }
+}
+
+/// Checks if the new event makes the queued one useless.
+fn can_coalesce(queued: &Event, event: &Event) -> bool {
+    match (queued, event) {
+        (Event::Mouse(queued), Event::Mouse(mouse)) => {
+            queued.event_type == MouseEventType::MouseMove
+                && mouse.event_type == MouseEventType::MouseMove
+                && queued.left_button == mouse.left_button
+                && queued.middle_button == mouse.middle_button
+                && queued.right_button == mouse.right_button
+                && queued.extra_button_1 == mouse.extra_button_1
+                && queued.extra_button_2 == mouse.extra_button_2
+                && queued.extra_button_3 == mouse.extra_button_3
+                && queued.extra_button_4 == mouse.extra_button_4
+        }
+        (Event::Window(queued), Event::Window(window)) => {
+            queued.event_type == WindowEventType::WindowResize
+                && window.event_type == WindowEventType::WindowResize
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::size_2d::Size2d;
+    use core::events::drop_policy::DropPolicy;
+    use core::events::event::{Event, MouseEvent, MouseEventType, WindowEvent, WindowEventType};
+    use core::events::event_queue::EventQueue;
+
+    fn text(text: &str) -> Event {
+        Event::Text(text.to_string())
+    }
+
+    fn mouse_move(x: i32, left_button: bool) -> Event {
+        Event::Mouse(MouseEvent {
+            event_type: MouseEventType::MouseMove,
+            left_button,
+            middle_button: false,
+            right_button: false,
+            extra_button_1: false,
+            extra_button_2: false,
+            extra_button_3: false,
+            extra_button_4: false,
+            position: Point2d::new(x, 0),
+        })
+    }
+
+    fn resize(width: usize) -> Event {
+        Event::Window(WindowEvent {
+            event_type: WindowEventType::WindowResize,
+            position: Point2d::empty(),
+            size: Size2d::new(width, 1),
+        })
+    }
+
+    /// Takes the queued events as text, with the mouse events as their
+    /// column and the resizes as their width.
+    fn get_texts(queue: &mut EventQueue) -> Vec<String> {
+        let mut texts = Vec::new();
+
+        while let Some(event) = queue.get_event() {
+            texts.push(match event {
+                Event::Text(text) => text,
+                Event::Mouse(mouse) => format!("m{}", mouse.position.x),
+                Event::Window(window) => format!("w{}", window.size.width),
+                event => format!("{:?}", event),
+            });
+        }
+
+        texts
+    }
+
+    #[test]
+    fn events_keep_their_order() {
+        let mut queue = EventQueue::new();
+        queue.add_event(text("a"));
+        queue.add_event(text("b"));
+        queue.add_event(text("c"));
+
+        assert_eq!(queue.len(), 3);
+        assert_eq!(get_texts(&mut queue), vec!["a", "b", "c"]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn full_queue_drops_the_oldest_event() {
+        let mut queue = EventQueue::new();
+        queue.set_capacity(Some(2));
+        queue.add_event(text("a"));
+        queue.add_event(text("b"));
+        queue.add_event(text("c"));
+
+        assert_eq!(queue.get_dropped_events(), 1);
+        assert_eq!(queue.get_total_events(), 3);
+        assert_eq!(get_texts(&mut queue), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn full_queue_drops_the_newest_event() {
+        let mut queue = EventQueue::new();
+        queue.set_capacity(Some(2));
+        queue.set_drop_policy(DropPolicy::DropNewest);
+        queue.add_event(text("a"));
+        queue.add_event(text("b"));
+        queue.add_event(text("c"));
+
+        assert_eq!(queue.get_dropped_events(), 1);
+        assert_eq!(get_texts(&mut queue), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn lower_capacity_drops_the_oldest_events() {
+        let mut queue = EventQueue::new();
+        queue.add_event(text("a"));
+        queue.add_event(text("b"));
+        queue.add_event(text("c"));
+        queue.set_capacity(Some(1));
+
+        assert_eq!(queue.get_dropped_events(), 2);
+        assert_eq!(queue.get_max_len(), 3);
+        assert_eq!(get_texts(&mut queue), vec!["c"]);
+    }
+
+    #[test]
+    fn coalescing_replaces_the_previous_move_and_resize() {
+        let mut queue = EventQueue::new();
+        queue.set_coalescing(true);
+        queue.add_event(mouse_move(1, false));
+        queue.add_event(mouse_move(2, false));
+        queue.add_event(mouse_move(3, true));
+        queue.add_event(resize(10));
+        queue.add_event(resize(20));
+        queue.add_event(text("a"));
+        queue.add_event(mouse_move(4, true));
+
+        assert_eq!(queue.get_total_events(), 7);
+        assert_eq!(get_texts(&mut queue), vec!["m2", "m3", "w20", "a", "m4"]);
+    }
+
+    #[test]
+    fn without_coalescing_every_move_is_kept() {
+        let mut queue = EventQueue::new();
+        queue.add_event(mouse_move(1, false));
+        queue.add_event(mouse_move(2, false));
+
+        assert_eq!(get_texts(&mut queue), vec!["m1", "m2"]);
+    }
+
+    #[test]
+    fn drain_where_keeps_the_other_events() {
+        let mut queue = EventQueue::new();
+        queue.add_event(text("a"));
+        queue.add_event(mouse_move(1, false));
+        queue.add_event(text("b"));
+        queue.add_event(mouse_move(2, false));
+
+        let drained = queue.drain_where(|event| matches!(event, Event::Mouse(_)));
+
+        assert_eq!(drained.len(), 2);
+        assert_eq!(get_texts(&mut queue), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn peek_keeps_the_event() {
+        let mut queue = EventQueue::new();
+        assert!(queue.peek().is_none());
+
+        queue.add_event(text("a"));
+        queue.add_event(mouse_move(1, false));
+
+        match queue.peek() {
+            Some(Event::Text(text)) => assert_eq!(text, "a"),
+            event => panic!("unexpected event {:?}", event),
+        }
+
+        match queue.peek_where(|event| matches!(event, Event::Mouse(_))) {
+            Some(Event::Mouse(mouse)) => assert_eq!(mouse.position.x, 1),
+            event => panic!("unexpected event {:?}", event),
+        }
+
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn sent_events_are_received_in_order() {
+        let mut queue = EventQueue::new();
+        let sender = queue.get_sender();
+        queue.add_event(text("a"));
+        sender.send(text("b")).unwrap();
+        sender.send(text("c")).unwrap();
+
+        assert_eq!(queue.len(), 1);
+
+        queue.receive_sent_events();
+        assert_eq!(queue.get_sent_events(), 2);
+        assert_eq!(get_texts(&mut queue), vec!["a", "b", "c"]);
+    }
 }
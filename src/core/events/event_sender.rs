@@ -0,0 +1,57 @@
+use core::error::Error;
+use core::events::event::Event;
+use core::events::waker::Waker;
+use core::Result;
+use std::any::Any;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+
+/// Sends events to the queue of an application from any thread. The events
+/// are added to the queue on the next `Application::listen_events` call, and
+/// an application waiting in `wait_events` wakes up to receive them.
+#[derive(Clone)]
+pub struct EventSender {
+    sender: Sender<Event>,
+    waker: Option<Arc<dyn Waker>>,
+}
+
+impl EventSender {
+    pub fn new(sender: Sender<Event>, waker: Option<Arc<dyn Waker>>) -> EventSender {
+        EventSender { sender, waker }
+    }
+
+    /// Sends an event. Fails with `Error::Disconnected` when the application
+    /// no longer exists.
+    pub fn send(&self, event: Event) -> Result<()> {
+        if self.sender.send(event).is_err() {
+            return Err(Error::Disconnected);
+        }
+
+        if let Some(ref waker) = self.waker {
+            waker.wake();
+        }
+
+        Ok(())
+    }
+
+    /// Sends a custom value as an `Event::User`.
+    pub fn send_user<T: Any + Send>(&self, value: T) -> Result<()> {
+        self.send(Event::User(Box::new(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::error::Error;
+    use core::events::event_queue::EventQueue;
+
+    #[test]
+    fn sending_to_a_dropped_queue_is_disconnected() {
+        let sender = EventQueue::new().get_sender();
+
+        match sender.send_user(1u8) {
+            Err(Error::Disconnected) => (),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
@@ -1,2 +1,6 @@
+pub mod drop_policy;
 pub mod event;
 pub mod event_queue;
+pub mod event_sender;
+pub mod timed_event;
+pub mod waker;
@@ -0,0 +1,16 @@
+use core::events::event::Event;
+use std::time::Instant;
+
+/// An event with the monotonic time the application received it.
+#[derive(Debug)]
+pub struct TimedEvent {
+    pub event: Event,
+    pub timestamp: Instant,
+}
+
+#[allow(dead_code)]
+impl TimedEvent {
+    pub fn new(event: Event, timestamp: Instant) -> TimedEvent {
+        TimedEvent { event, timestamp }
+    }
+}
@@ -0,0 +1,5 @@
+/// Wakes up an application waiting for native events, so it notices the
+/// events sent from other threads.
+pub trait Waker: Send + Sync {
+    fn wake(&self);
+}
@@ -0,0 +1,93 @@
+use std::time::{Duration, Instant};
+
+/// Frame and update timing statistics collected by the game runner.
+#[derive(Debug, Copy, Clone)]
+pub struct FrameStats {
+    fps: u32,
+    ups: u32,
+    frame_time: Duration,
+    frame_count: u64,
+    update_count: u64,
+    last_frame: Option<Instant>,
+    second_start: Instant,
+    second_frames: u32,
+    second_updates: u32,
+}
+
+#[allow(dead_code)]
+impl FrameStats {
+    pub fn new() -> FrameStats {
+        FrameStats {
+            fps: 0,
+            ups: 0,
+            frame_time: Duration::from_secs(0),
+            frame_count: 0,
+            update_count: 0,
+            last_frame: None,
+            second_start: Instant::now(),
+            second_frames: 0,
+            second_updates: 0,
+        }
+    }
+
+    /// Gets the frames presented during the last complete second.
+    #[inline]
+    pub fn get_fps(&self) -> u32 {
+        self.fps
+    }
+
+    /// Gets the updates done during the last complete second.
+    #[inline]
+    pub fn get_ups(&self) -> u32 {
+        self.ups
+    }
+
+    /// Gets the time between the last two presented frames.
+    #[inline]
+    pub fn get_frame_time(&self) -> Duration {
+        self.frame_time
+    }
+
+    #[inline]
+    pub fn get_frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    #[inline]
+    pub fn get_update_count(&self) -> u64 {
+        self.update_count
+    }
+
+    pub fn record_update(&mut self) {
+        self.update_count += 1;
+        self.second_updates += 1;
+    }
+
+    pub fn record_frame(&mut self, now: Instant) {
+        if let Some(last_frame) = self.last_frame {
+            self.frame_time = now - last_frame;
+        }
+
+        self.last_frame = Some(now);
+        self.frame_count += 1;
+        self.second_frames += 1;
+
+        let elapsed = now - self.second_start;
+
+        if elapsed >= Duration::from_secs(1) {
+            // scales the counts in case the second was a bit longer.
+            let seconds = elapsed.as_secs_f64();
+            self.fps = (self.second_frames as f64 / seconds).round() as u32;
+            self.ups = (self.second_updates as f64 / seconds).round() as u32;
+            self.second_start = now;
+            self.second_frames = 0;
+            self.second_updates = 0;
+        }
+    }
+}
+
+impl Default for FrameStats {
+    fn default() -> FrameStats {
+        FrameStats::new()
+    }
+}
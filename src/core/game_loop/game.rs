@@ -0,0 +1,34 @@
+use core::application::Application;
+use core::drawing::cell_buffer::CellBuffer;
+use core::events::event::Event;
+use core::game_loop::frame_stats::FrameStats;
+use std::time::Duration;
+
+/// The callbacks the game runner calls on every frame.
+pub trait Game {
+    /// Handles an event taken from the application event queue. The
+    /// application gives access to the mouse and keyboard state, which
+    /// already includes every event of the current input frame.
+    fn handle_event(&mut self, event: Event, application: &dyn Application);
+
+    /// Advances the game state. In real time mode `dt` is always the fixed
+    /// timestep, in turn based mode it is the time since the previous update.
+    /// The application gives access to the input state of the current frame.
+    fn update(&mut self, dt: Duration, application: &dyn Application);
+
+    /// Draws the game onto the buffer that will be presented. The buffer
+    /// keeps the content of the previous frame.
+    fn draw(&mut self, buffer: &mut CellBuffer);
+
+    /// Receives, before drawing, how far the current time is between the
+    /// last update and the next one, from 0 to 1, to smooth the movements.
+    fn interpolate(&mut self, _alpha: f64) {}
+
+    /// Receives the timing statistics after every presented frame.
+    fn end_frame(&mut self, _stats: &FrameStats) {}
+
+    /// Stops the runner when it returns true.
+    fn should_quit(&self) -> bool {
+        false
+    }
+}
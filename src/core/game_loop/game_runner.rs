@@ -0,0 +1,225 @@
+use core::application::Application;
+use core::drawing::cell::Cell;
+use core::drawing::cell_buffer::CellBuffer;
+use core::events::event::{Event, WindowEventType};
+use core::game_loop::frame_stats::FrameStats;
+use core::game_loop::game::Game;
+use core::game_loop::loop_mode::LoopMode;
+use core::Result;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// Runs a game on any application: dispatches the events, updates the game
+/// with a fixed timestep, draws it and presents the frames on the terminal.
+/// The buffer follows the console size, and a window close event stops the
+/// runner after the game handled it.
+#[derive(Debug)]
+pub struct GameRunner {
+    mode: LoopMode,
+    timestep: Duration,
+    fps_limit: Option<u32>,
+    max_frame_time: Duration,
+    default_cell: Cell,
+    stats: FrameStats,
+}
+
+#[allow(dead_code)]
+impl GameRunner {
+    pub fn new() -> GameRunner {
+        GameRunner {
+            mode: LoopMode::RealTime,
+            timestep: Duration::from_secs(1) / 60,
+            fps_limit: Some(60),
+            max_frame_time: Duration::from_millis(250),
+            default_cell: Cell::new_default(' '),
+            stats: FrameStats::new(),
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: LoopMode) {
+        self.mode = mode;
+    }
+
+    /// Sets the amount of fixed timestep updates per second.
+    pub fn set_update_rate(&mut self, updates_per_second: u32) {
+        self.timestep = Duration::from_secs(1) / updates_per_second.max(1);
+    }
+
+    /// Sets the maximum frames drawn per second, or none to draw as fast as
+    /// possible.
+    pub fn set_fps_limit(&mut self, fps_limit: Option<u32>) {
+        self.fps_limit = fps_limit.map(|fps| fps.max(1));
+    }
+
+    /// Sets the longest time simulated in a single frame, so a slow frame
+    /// doesn't cause an ever growing amount of updates.
+    pub fn set_max_frame_time(&mut self, max_frame_time: Duration) {
+        self.max_frame_time = max_frame_time;
+    }
+
+    /// Sets the cell used to fill the buffer when it is created or resized.
+    pub fn set_default_cell(&mut self, default_cell: Cell) {
+        self.default_cell = default_cell;
+    }
+
+    #[inline]
+    pub fn get_stats(&self) -> &FrameStats {
+        &self.stats
+    }
+
+    /// Runs the game until it quits or the window is closed.
+    pub fn run(&mut self, application: &mut dyn Application, game: &mut dyn Game) -> Result<()> {
+        let console_size = application.get_terminal().get_console_size()?;
+        let mut buffer = CellBuffer::new(self.default_cell, console_size);
+        self.stats = FrameStats::new();
+
+        match self.mode {
+            LoopMode::RealTime => self.run_real_time(application, game, &mut buffer),
+            LoopMode::TurnBased => self.run_turn_based(application, game, &mut buffer),
+        }
+    }
+
+    fn run_real_time(
+        &mut self,
+        application: &mut dyn Application,
+        game: &mut dyn Game,
+        buffer: &mut CellBuffer,
+    ) -> Result<()> {
+        let mut accumulator = Duration::from_secs(0);
+        let mut previous = Instant::now();
+        let mut next_frame = previous;
+
+        loop {
+            // every frame listens the events once, so the key presses are
+            // kept until the game updates.
+            match self.fps_limit {
+                Some(fps_limit) => {
+                    // sleeps until the next frame or until there is input,
+                    // then waits the rest of the frame.
+                    let now = Instant::now();
+
+                    if now < next_frame {
+                        application.wait_events(Some(next_frame - now))?;
+                    } else {
+                        application.listen_events()?;
+                    }
+
+                    let now = Instant::now();
+
+                    if now < next_frame {
+                        sleep(next_frame - now);
+                    }
+
+                    // a late frame moves the schedule instead of rushing the
+                    // next frames.
+                    next_frame += Duration::from_secs(1) / fps_limit;
+                    next_frame = next_frame.max(Instant::now());
+                }
+                None => application.listen_events()?,
+            }
+
+            if self.dispatch_events(application, game, buffer) {
+                return Ok(());
+            }
+
+            let now = Instant::now();
+            accumulator += (now - previous).min(self.max_frame_time);
+            previous = now;
+
+            while accumulator >= self.timestep {
+                game.update(self.timestep, application);
+                self.stats.record_update();
+                accumulator -= self.timestep;
+            }
+
+            game.interpolate(accumulator.as_secs_f64() / self.timestep.as_secs_f64());
+            self.present(application, game, buffer)?;
+        }
+    }
+
+    fn run_turn_based(
+        &mut self,
+        application: &mut dyn Application,
+        game: &mut dyn Game,
+        buffer: &mut CellBuffer,
+    ) -> Result<()> {
+        let mut previous = Instant::now();
+
+        // the first frame is drawn before there is any input.
+        game.update(Duration::from_secs(0), application);
+        self.stats.record_update();
+        self.present(application, game, buffer)?;
+
+        loop {
+            application.wait_events(None)?;
+
+            if self.dispatch_events(application, game, buffer) {
+                return Ok(());
+            }
+
+            let now = Instant::now();
+            game.update(now - previous, application);
+            self.stats.record_update();
+            previous = now;
+
+            self.present(application, game, buffer)?;
+        }
+    }
+
+    /// Passes the queued events to the game. Returns true when the runner
+    /// must stop.
+    fn dispatch_events(
+        &self,
+        application: &mut dyn Application,
+        game: &mut dyn Game,
+        buffer: &mut CellBuffer,
+    ) -> bool {
+        while let Some(event) = application.get_mut_event_queue().get_event() {
+            let mut close = false;
+
+            if let Event::Window(window) = event {
+                match window.event_type {
+                    WindowEventType::WindowResize => buffer.resize(self.default_cell, window.size),
+                    WindowEventType::WindowClose => close = true,
+                    _ => (),
+                }
+            }
+
+            game.handle_event(event, application);
+
+            if close || game.should_quit() {
+                return true;
+            }
+        }
+
+        game.should_quit()
+    }
+
+    fn present(
+        &mut self,
+        application: &mut dyn Application,
+        game: &mut dyn Game,
+        buffer: &mut CellBuffer,
+    ) -> Result<()> {
+        game.draw(buffer);
+
+        // the buffer lives across frames, so its dirty regions are the
+        // cells that changed since the previous write. A failed write may
+        // have sent only some of them, so the next one sends everything.
+        let regions = buffer.take_dirty_regions();
+
+        if let Err(error) = application.get_terminal().write_regions(buffer, &regions) {
+            buffer.mark_all_dirty();
+            return Err(error);
+        }
+        self.stats.record_frame(Instant::now());
+        game.end_frame(&self.stats);
+        Ok(())
+    }
+}
+
+impl Default for GameRunner {
+    fn default() -> GameRunner {
+        GameRunner::new()
+    }
+}
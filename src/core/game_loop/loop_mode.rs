@@ -0,0 +1,9 @@
+/// Enumerates the ways the game runner schedules the frames.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LoopMode {
+    /// Updates at a fixed rate and draws continuously, up to the FPS limit.
+    RealTime,
+
+    /// Sleeps until there is input, then updates and draws once.
+    TurnBased,
+}
@@ -0,0 +1,4 @@
+pub mod frame_stats;
+pub mod game;
+pub mod game_runner;
+pub mod loop_mode;
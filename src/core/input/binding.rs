@@ -0,0 +1,59 @@
+use core::input::key_chord::KeyChord;
+use core::input::mouse_button::MouseButton;
+use std::fmt::{Display, Formatter, Result as FormatResult};
+
+/// An input that triggers an action.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Binding {
+    /// A key with an exact set of modifiers.
+    Chord(KeyChord),
+
+    /// A mouse button.
+    Mouse(MouseButton),
+
+    /// Chords pressed one after the other, like `G G`.
+    Sequence(Vec<KeyChord>),
+}
+
+#[allow(dead_code)]
+impl Binding {
+    /// Parses a binding: a chord like `Ctrl+S`, a mouse button like
+    /// `MouseLeft`, or a sequence of chords separated by spaces like `G G`.
+    pub fn parse(text: &str) -> Option<Binding> {
+        if let Some(button) = MouseButton::from_name(text.trim()) {
+            return Some(Binding::Mouse(button));
+        }
+
+        let mut chords = Vec::new();
+
+        for part in text.split_whitespace() {
+            chords.push(KeyChord::parse(part)?);
+        }
+
+        match chords.len() {
+            0 => None,
+            1 => Some(Binding::Chord(chords[0])),
+            _ => Some(Binding::Sequence(chords)),
+        }
+    }
+}
+
+impl Display for Binding {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Binding::Chord(chord) => write!(formatter, "{}", chord),
+            Binding::Mouse(button) => formatter.write_str(button.get_name()),
+            Binding::Sequence(chords) => {
+                for (index, chord) in chords.iter().enumerate() {
+                    if index > 0 {
+                        formatter.write_str(" ")?;
+                    }
+
+                    write!(formatter, "{}", chord)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
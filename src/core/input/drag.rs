@@ -0,0 +1,26 @@
+use core::drawing::point_2d::Point2d;
+use core::drawing::rect::Rect;
+use core::input::drag_phase::DragPhase;
+use core::input::mouse_button::MouseButton;
+
+/// A mouse movement with a button held, in character cells.
+#[derive(Debug, Copy, Clone)]
+pub struct Drag {
+    pub button: MouseButton,
+    pub phase: DragPhase,
+
+    /// The cell where the button was pressed.
+    pub origin: Point2d,
+
+    /// The cell where the mouse is.
+    pub position: Point2d,
+}
+
+#[allow(dead_code)]
+impl Drag {
+    /// Gets the rectangle of cells that goes from the origin to the current
+    /// position, both included.
+    pub fn get_rect(&self) -> Rect {
+        Rect::from_corners(self.origin, self.position)
+    }
+}
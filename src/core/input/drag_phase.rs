@@ -0,0 +1,12 @@
+/// Enumerates the phases of a drag during a frame.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DragPhase {
+    /// The mouse left the pressed cell during the frame.
+    Started,
+
+    /// The drag was already going on before the frame.
+    Moved,
+
+    /// The button was released during the frame.
+    Ended,
+}
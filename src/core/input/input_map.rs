@@ -0,0 +1,604 @@
+use core::error::Error;
+use core::events::event::{Event, KeyboardEventType};
+use core::input::binding::Binding;
+use core::input::key::Key;
+use core::input::key_chord::KeyChord;
+use core::input::keyboard_state::KeyboardState;
+use core::input::modifiers::Modifiers;
+use core::input::mouse_button::{MouseButton, ALL_MOUSE_BUTTONS};
+use core::input::mouse_state::MouseState;
+use core::Result;
+use std::fmt::Display;
+use std::fs::{read_to_string, write};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Maps the inputs to user defined actions. Every action can have several
+/// bindings, and the same binding can trigger several actions.
+///
+/// Directions given to the presets go clockwise from north: north,
+/// north east, east, south east, south, south west, west and north west.
+pub struct InputMap<A> {
+    bindings: Vec<(A, Binding)>,
+    sequence: Vec<KeyChord>,
+    held_keys: Vec<Key>,
+    last_chord_time: Option<Instant>,
+    sequence_timeout: Duration,
+    mouse_buttons: [bool; 7],
+}
+
+#[allow(dead_code)]
+impl<A: Copy + PartialEq> InputMap<A> {
+    pub fn new() -> InputMap<A> {
+        InputMap {
+            bindings: Vec::new(),
+            sequence: Vec::new(),
+            held_keys: Vec::new(),
+            last_chord_time: None,
+            sequence_timeout: Duration::from_secs(1),
+            mouse_buttons: [false; 7],
+        }
+    }
+
+    pub fn bind(&mut self, action: A, binding: Binding) {
+        if !self.bindings.contains(&(action, binding.clone())) {
+            self.bindings.push((action, binding));
+        }
+    }
+
+    pub fn bind_key(&mut self, action: A, key: Key) {
+        self.bind(action, Binding::Chord(KeyChord::from_key(key)));
+    }
+
+    pub fn unbind(&mut self, action: A, binding: &Binding) {
+        self.bindings
+            .retain(|(bound_action, bound)| *bound_action != action || bound != binding);
+    }
+
+    /// Removes every binding of the action.
+    pub fn clear_action(&mut self, action: A) {
+        self.bindings
+            .retain(|(bound_action, _)| *bound_action != action);
+    }
+
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+        self.sequence.clear();
+    }
+
+    pub fn get_bindings(&self, action: A) -> Vec<&Binding> {
+        self.bindings
+            .iter()
+            .filter(|(bound_action, _)| *bound_action == action)
+            .map(|(_, binding)| binding)
+            .collect()
+    }
+
+    /// Sets the longest time between the chords of a sequence.
+    pub fn set_sequence_timeout(&mut self, sequence_timeout: Duration) {
+        self.sequence_timeout = sequence_timeout;
+    }
+
+    /// Binds the directions to the numpad, both with num lock on (the digits)
+    /// and off (the arrows, home, end and the page keys).
+    pub fn bind_numpad(&mut self, directions: [A; 8]) {
+        let keys = [
+            (Key::NumPad8, Key::Up),
+            (Key::NumPad9, Key::Prior),
+            (Key::NumPad6, Key::Right),
+            (Key::NumPad3, Key::Next),
+            (Key::NumPad2, Key::Down),
+            (Key::NumPad1, Key::End),
+            (Key::NumPad4, Key::Left),
+            (Key::NumPad7, Key::Home),
+        ];
+
+        for (action, (digit, navigation)) in directions.iter().zip(keys.iter()) {
+            self.bind_key(*action, *digit);
+            self.bind_key(*action, *navigation);
+        }
+    }
+
+    /// Binds the directions to the vi keys: `hjkl` and `yubn`.
+    pub fn bind_vi_keys(&mut self, directions: [A; 8]) {
+        let keys = [
+            Key::K,
+            Key::U,
+            Key::L,
+            Key::N,
+            Key::J,
+            Key::B,
+            Key::H,
+            Key::Y,
+        ];
+
+        for (action, key) in directions.iter().zip(keys.iter()) {
+            self.bind_key(*action, *key);
+        }
+    }
+
+    /// Gets the actions triggered by an event: a key down that matches a
+    /// chord or completes a sequence, or a mouse button going down. The
+    /// modifier keys and the key repeats don't take part in the sequences.
+    pub fn get_actions(&mut self, event: &Event) -> Vec<A> {
+        let mut triggered = Vec::new();
+
+        match event {
+            Event::Keyboard(keyboard) if keyboard.event_type == KeyboardEventType::KeyDown => {
+                let chord = KeyChord::new(keyboard.key, Modifiers::from_event(keyboard));
+                let repeat = self.held_keys.contains(&keyboard.key);
+                let sequenced = !repeat && !keyboard.key.is_modifier();
+
+                if !repeat {
+                    self.held_keys.push(keyboard.key);
+                }
+
+                if sequenced {
+                    self.push_chord(chord);
+                }
+
+                for (action, binding) in self.bindings.iter() {
+                    let matches = match binding {
+                        Binding::Chord(bound) => *bound == chord,
+                        Binding::Sequence(chords) => sequenced && self.sequence.ends_with(chords),
+                        Binding::Mouse(_) => false,
+                    };
+
+                    if matches && !triggered.contains(action) {
+                        triggered.push(*action);
+                    }
+                }
+
+                // a completed sequence doesn't start the next one.
+                if sequenced
+                    && self.bindings.iter().any(|(_, binding)| match binding {
+                        Binding::Sequence(chords) => self.sequence.ends_with(chords),
+                        _ => false,
+                    })
+                {
+                    self.sequence.clear();
+                }
+            }
+            Event::Keyboard(keyboard) => self.held_keys.retain(|key| *key != keyboard.key),
+            Event::Mouse(mouse) => {
+                for (index, button) in ALL_MOUSE_BUTTONS.iter().enumerate() {
+                    let down = button.is_down_in(mouse);
+
+                    if down && !self.mouse_buttons[index] {
+                        self.push_button_actions(*button, &mut triggered);
+                    }
+
+                    self.mouse_buttons[index] = down;
+                }
+            }
+            _ => (),
+        }
+
+        triggered
+    }
+
+    /// Checks if a chord or a mouse button of the action was pressed during
+    /// the current frame.
+    pub fn is_pressed(
+        &self,
+        action: A,
+        keyboard_state: &KeyboardState,
+        mouse_state: &MouseState,
+    ) -> bool {
+        self.bindings.iter().any(|(bound_action, binding)| {
+            *bound_action == action
+                && match binding {
+                    Binding::Chord(chord) => keyboard_state.is_chord_pressed(*chord),
+                    Binding::Mouse(button) => mouse_state.is_pressed(*button),
+                    Binding::Sequence(_) => false,
+                }
+        })
+    }
+
+    /// Checks if a chord or a mouse button of the action is held down.
+    pub fn is_down(
+        &self,
+        action: A,
+        keyboard_state: &KeyboardState,
+        mouse_state: &MouseState,
+    ) -> bool {
+        self.bindings.iter().any(|(bound_action, binding)| {
+            *bound_action == action
+                && match binding {
+                    Binding::Chord(chord) => keyboard_state.is_chord_down(*chord),
+                    Binding::Mouse(button) => mouse_state.is_down(*button),
+                    Binding::Sequence(_) => false,
+                }
+        })
+    }
+
+    fn push_chord(&mut self, chord: KeyChord) {
+        let now = Instant::now();
+
+        if self
+            .last_chord_time
+            .is_some_and(|last| now - last > self.sequence_timeout)
+        {
+            self.sequence.clear();
+        }
+
+        let longest = self
+            .bindings
+            .iter()
+            .map(|(_, binding)| match binding {
+                Binding::Sequence(chords) => chords.len(),
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0);
+
+        self.sequence.push(chord);
+
+        if self.sequence.len() > longest {
+            let excess = self.sequence.len() - longest;
+            self.sequence.drain(..excess);
+        }
+
+        self.last_chord_time = Some(now);
+    }
+
+    fn push_button_actions(&self, button: MouseButton, triggered: &mut Vec<A>) {
+        for (action, binding) in self.bindings.iter() {
+            if *binding == Binding::Mouse(button) && !triggered.contains(action) {
+                triggered.push(*action);
+            }
+        }
+    }
+}
+
+/// Loading and saving of the bindings as text, one action per line:
+///
+/// ```text
+/// # comments start with a hash
+/// MoveNorth = Up, NumPad8, K
+/// Save = Ctrl+S
+/// Attack = MouseLeft
+/// GoToTop = G G
+/// ```
+#[allow(dead_code)]
+impl<A: Copy + PartialEq + Display + FromStr> InputMap<A> {
+    /// Loads the bindings of a configuration text. The actions in the text
+    /// replace all their previous bindings, and the rest are kept.
+    pub fn load_config(&mut self, text: &str) -> Result<()> {
+        let mut loaded: Vec<(A, Vec<Binding>)> = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |message| Error::InvalidConfig {
+                line: index + 1,
+                message,
+            };
+
+            let (name, bindings) = line
+                .split_once('=')
+                .ok_or_else(|| invalid("Expected an action name followed by `=`."))?;
+
+            let action = A::from_str(name.trim()).map_err(|_| invalid("Unknown action."))?;
+            let mut parsed = Vec::new();
+
+            for binding in bindings
+                .split(',')
+                .filter(|binding| !binding.trim().is_empty())
+            {
+                parsed.push(Binding::parse(binding).ok_or_else(|| invalid("Invalid binding."))?);
+            }
+
+            loaded.push((action, parsed));
+        }
+
+        for (action, _) in loaded.iter() {
+            self.clear_action(*action);
+        }
+
+        for (action, bindings) in loaded {
+            for binding in bindings {
+                self.bind(action, binding);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Formats the bindings as a configuration text.
+    pub fn to_config(&self) -> String {
+        let mut actions: Vec<A> = Vec::new();
+
+        for (action, _) in self.bindings.iter() {
+            if !actions.contains(action) {
+                actions.push(*action);
+            }
+        }
+
+        let mut text = String::new();
+
+        for action in actions {
+            let bindings: Vec<String> = self
+                .get_bindings(action)
+                .iter()
+                .map(|binding| binding.to_string())
+                .collect();
+
+            text.push_str(&format!("{} = {}\n", action, bindings.join(", ")));
+        }
+
+        text
+    }
+
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let text = read_to_string(path)?;
+        self.load_config(&text)
+    }
+
+    pub fn save_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        write(path, self.to_config())?;
+        Ok(())
+    }
+}
+
+impl<A: Copy + PartialEq> Default for InputMap<A> {
+    fn default() -> InputMap<A> {
+        InputMap::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::point_2d::Point2d;
+    use core::error::Error;
+    use core::events::event::{
+        Event, KeyboardEvent, KeyboardEventType, MouseEvent, MouseEventType,
+    };
+    use core::input::binding::Binding;
+    use core::input::input_map::InputMap;
+    use core::input::key::Key;
+    use core::input::key_chord::KeyChord;
+    use core::input::keyboard_state::KeyboardState;
+    use core::input::modifiers::Modifiers;
+    use core::input::mouse_button::MouseButton;
+    use core::input::mouse_state::MouseState;
+    use std::env::temp_dir;
+    use std::fmt::{Display, Formatter, Result as FormatResult};
+    use std::fs::remove_file;
+    use std::process;
+    use std::str::FromStr;
+
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    enum Action {
+        Attack,
+        Save,
+        GoToTop,
+    }
+
+    impl Display for Action {
+        fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+            write!(formatter, "{:?}", self)
+        }
+    }
+
+    impl FromStr for Action {
+        type Err = ();
+
+        fn from_str(name: &str) -> Result<Action, ()> {
+            match name {
+                "Attack" => Ok(Action::Attack),
+                "Save" => Ok(Action::Save),
+                "GoToTop" => Ok(Action::GoToTop),
+                _ => Err(()),
+            }
+        }
+    }
+
+    fn create_map() -> InputMap<Action> {
+        let mut map = InputMap::new();
+        map.bind_key(Action::Attack, Key::F);
+        map.bind(Action::Attack, Binding::Mouse(MouseButton::Left));
+        map.bind(
+            Action::Save,
+            Binding::Chord(KeyChord::new(Key::S, Modifiers::CONTROL)),
+        );
+        map.bind(
+            Action::GoToTop,
+            Binding::Sequence(vec![KeyChord::from_key(Key::G); 2]),
+        );
+        map
+    }
+
+    /// Gets the line and message of an invalid configuration.
+    fn get_config_error(text: &str) -> (usize, &'static str) {
+        match InputMap::<Action>::new().load_config(text) {
+            Err(Error::InvalidConfig { line, message }) => (line, message),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    fn key_event(key: Key, down: bool, left_shift: bool) -> Event {
+        Event::Keyboard(KeyboardEvent {
+            event_type: if down {
+                KeyboardEventType::KeyDown
+            } else {
+                KeyboardEventType::KeyUp
+            },
+            key,
+            key_code: 0,
+            character: '\0',
+            left_control: false,
+            left_shift,
+            left_menu: false,
+            right_control: false,
+            right_shift: false,
+            right_menu: false,
+        })
+    }
+
+    /// Presses and releases a key, like a terminal without key releases.
+    fn tap(map: &mut InputMap<Action>, key: Key, left_shift: bool) -> Vec<Action> {
+        let actions = map.get_actions(&key_event(key, true, left_shift));
+        map.get_actions(&key_event(key, false, left_shift));
+        actions
+    }
+
+    fn mouse_event(left_button: bool) -> MouseEvent {
+        MouseEvent {
+            event_type: if left_button {
+                MouseEventType::Click
+            } else {
+                MouseEventType::MouseMove
+            },
+            left_button,
+            middle_button: false,
+            right_button: false,
+            extra_button_1: false,
+            extra_button_2: false,
+            extra_button_3: false,
+            extra_button_4: false,
+            position: Point2d::new(1, 1),
+        }
+    }
+
+    #[test]
+    fn mouse_binding_reports_the_press_edge() {
+        let mut map = InputMap::new();
+        map.bind(Action::Attack, Binding::Mouse(MouseButton::Left));
+        let keyboard_state = KeyboardState::new();
+        let mut mouse_state = MouseState::new();
+
+        mouse_state.begin_frame();
+        mouse_state.update_from_event(&mut mouse_event(true));
+        assert!(map.is_pressed(Action::Attack, &keyboard_state, &mouse_state));
+        assert!(map.is_down(Action::Attack, &keyboard_state, &mouse_state));
+        assert!(!map.is_pressed(Action::Save, &keyboard_state, &mouse_state));
+
+        // held in the next frame, it is down but not pressed again.
+        mouse_state.begin_frame();
+        assert!(!map.is_pressed(Action::Attack, &keyboard_state, &mouse_state));
+        assert!(map.is_down(Action::Attack, &keyboard_state, &mouse_state));
+
+        mouse_state.begin_frame();
+        mouse_state.update_from_event(&mut mouse_event(false));
+        assert!(!map.is_down(Action::Attack, &keyboard_state, &mouse_state));
+    }
+
+    #[test]
+    fn sequence_ignores_the_modifier_keys() {
+        let mut map = InputMap::new();
+        let shift_g = KeyChord::parse("Shift+G").unwrap();
+        map.bind(
+            Action::GoToTop,
+            Binding::Sequence(vec![KeyChord::from_key(Key::G), shift_g]),
+        );
+
+        assert!(tap(&mut map, Key::G, false).is_empty());
+        assert!(map
+            .get_actions(&key_event(Key::LeftShift, true, true))
+            .is_empty());
+        assert_eq!(tap(&mut map, Key::G, true), vec![Action::GoToTop]);
+    }
+
+    #[test]
+    fn sequence_ignores_the_key_repeats() {
+        let mut map = InputMap::new();
+        let g = KeyChord::from_key(Key::G);
+        map.bind(Action::GoToTop, Binding::Sequence(vec![g, g]));
+        map.bind(Action::Attack, Binding::Chord(g));
+
+        // holding the key repeats the chord, but doesn't complete the
+        // sequence.
+        assert_eq!(
+            map.get_actions(&key_event(Key::G, true, false)),
+            vec![Action::Attack]
+        );
+        assert_eq!(
+            map.get_actions(&key_event(Key::G, true, false)),
+            vec![Action::Attack]
+        );
+        map.get_actions(&key_event(Key::G, false, false));
+
+        assert_eq!(
+            tap(&mut map, Key::G, false),
+            vec![Action::GoToTop, Action::Attack]
+        );
+    }
+
+    #[test]
+    fn config_round_trips() {
+        let map = create_map();
+        let text = map.to_config();
+
+        assert_eq!(
+            text,
+            "Attack = F, MouseLeft\nSave = Ctrl+S\nGoToTop = G G\n"
+        );
+
+        let mut loaded = InputMap::<Action>::new();
+        loaded.load_config(&text).unwrap();
+        assert_eq!(loaded.to_config(), text);
+    }
+
+    #[test]
+    fn load_config_replaces_only_the_loaded_actions() {
+        let mut map = create_map();
+        map.load_config("# attack with the space bar\n\nAttack = Space\n")
+            .unwrap();
+
+        assert_eq!(
+            map.get_bindings(Action::Attack),
+            vec![&Binding::Chord(KeyChord::from_key(Key::Space))]
+        );
+        assert_eq!(map.get_bindings(Action::Save).len(), 1);
+        assert_eq!(map.get_bindings(Action::GoToTop).len(), 1);
+    }
+
+    #[test]
+    fn load_config_reports_the_invalid_line() {
+        assert_eq!(get_config_error("Save = Ctrl+S\nAttack F").0, 2);
+        assert_eq!(get_config_error("Jump = Space").0, 1);
+        assert_eq!(get_config_error("Save = Ctrl+Unknown").0, 1);
+        assert_eq!(get_config_error("Save = Ctrl+").0, 1);
+        assert_eq!(get_config_error("Save = Super+S").0, 1);
+        assert_eq!(get_config_error("\n# comment\nSave = S, Nothing").0, 3);
+    }
+
+    #[test]
+    fn invalid_config_keeps_the_bindings() {
+        let mut map = create_map();
+        let text = map.to_config();
+
+        assert!(map.load_config("Attack = Space\nSave = Unknown").is_err());
+        assert_eq!(map.to_config(), text);
+    }
+
+    #[test]
+    fn file_round_trips() {
+        let path = temp_dir().join(format!("rustlike-input-map-{}.cfg", process::id()));
+        let map = create_map();
+        map.save_file(&path).unwrap();
+
+        let mut loaded = InputMap::<Action>::new();
+        let result = loaded.load_file(&path);
+        remove_file(&path).unwrap();
+
+        result.unwrap();
+        assert_eq!(loaded.to_config(), map.to_config());
+    }
+
+    #[test]
+    fn load_file_reports_a_missing_file() {
+        let path = temp_dir().join("rustlike-input-map-missing.cfg");
+
+        match InputMap::<Action>::new().load_file(&path) {
+            Err(Error::Io(_)) => (),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
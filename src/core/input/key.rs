@@ -1,4 +1,6 @@
-#[allow(dead_code)]
+use std::fmt::{Display, Formatter, Result as FormatResult};
+
+#[allow(dead_code, clippy::enum_variant_names)]
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Key {
     None = 0,
@@ -173,8 +175,182 @@ pub enum Key {
     OemClear = 170,
 }
 
+/// Every key, in the order of their numeric value.
+pub const ALL_KEYS: [Key; 170] = [
+    Key::None,
+    Key::LeftButton,
+    Key::RightButton,
+    Key::Cancel,
+    Key::MiddleButton,
+    Key::XButton1,
+    Key::XButton2,
+    Key::Back,
+    Key::Tab,
+    Key::Clear,
+    Key::Return,
+    Key::Shift,
+    Key::Control,
+    Key::Menu,
+    Key::Pause,
+    Key::Capital,
+    Key::KanaHangelHangul,
+    Key::Junja,
+    Key::Final,
+    Key::HanjaKanji,
+    Key::Escape,
+    Key::Convert,
+    Key::NonConvert,
+    Key::Accept,
+    Key::ModeChange,
+    Key::Space,
+    Key::Prior,
+    Key::Next,
+    Key::End,
+    Key::Home,
+    Key::Left,
+    Key::Up,
+    Key::Right,
+    Key::Down,
+    Key::Select,
+    Key::Print,
+    Key::Execute,
+    Key::Snapshot,
+    Key::Insert,
+    Key::Delete,
+    Key::Help,
+    Key::Key0,
+    Key::Key1,
+    Key::Key2,
+    Key::Key3,
+    Key::Key4,
+    Key::Key5,
+    Key::Key6,
+    Key::Key7,
+    Key::Key8,
+    Key::Key9,
+    Key::A,
+    Key::B,
+    Key::C,
+    Key::D,
+    Key::E,
+    Key::F,
+    Key::G,
+    Key::H,
+    Key::I,
+    Key::J,
+    Key::K,
+    Key::L,
+    Key::M,
+    Key::N,
+    Key::O,
+    Key::P,
+    Key::Q,
+    Key::R,
+    Key::S,
+    Key::T,
+    Key::U,
+    Key::V,
+    Key::W,
+    Key::X,
+    Key::Y,
+    Key::Z,
+    Key::LeftWin,
+    Key::RightWin,
+    Key::Apps,
+    Key::Sleep,
+    Key::NumPad0,
+    Key::NumPad1,
+    Key::NumPad2,
+    Key::NumPad3,
+    Key::NumPad4,
+    Key::NumPad5,
+    Key::NumPad6,
+    Key::NumPad7,
+    Key::NumPad8,
+    Key::NumPad9,
+    Key::Multiply,
+    Key::Add,
+    Key::Separator,
+    Key::Subtract,
+    Key::Decimal,
+    Key::Divide,
+    Key::F1,
+    Key::F2,
+    Key::F3,
+    Key::F4,
+    Key::F5,
+    Key::F6,
+    Key::F7,
+    Key::F8,
+    Key::F9,
+    Key::F10,
+    Key::F11,
+    Key::F12,
+    Key::F13,
+    Key::F14,
+    Key::F15,
+    Key::F16,
+    Key::F17,
+    Key::F18,
+    Key::F19,
+    Key::F20,
+    Key::F21,
+    Key::F22,
+    Key::F23,
+    Key::F24,
+    Key::NumLock,
+    Key::Scroll,
+    Key::LeftShift,
+    Key::RightShift,
+    Key::LeftControl,
+    Key::RightControl,
+    Key::LeftMenu,
+    Key::RightMenu,
+    Key::BrowserBack,
+    Key::BrowserForward,
+    Key::BrowserRefresh,
+    Key::BrowserStop,
+    Key::BrowserSearch,
+    Key::BrowserFavorites,
+    Key::BrowserHome,
+    Key::VolumeMute,
+    Key::VolumeDown,
+    Key::VolumeUp,
+    Key::MediaNextTrack,
+    Key::MediaPreviousTrack,
+    Key::MediaStop,
+    Key::MediaPlayPause,
+    Key::LaunchMail,
+    Key::LaunchMediaSelect,
+    Key::LaunchApp1,
+    Key::LaunchApp2,
+    Key::Oem1,
+    Key::Plus,
+    Key::Comma,
+    Key::Minus,
+    Key::Period,
+    Key::Oem2,
+    Key::Oem3,
+    Key::Oem4,
+    Key::Oem5,
+    Key::Oem6,
+    Key::Oem7,
+    Key::Oem8,
+    Key::ProcessKey,
+    Key::ImeProcess,
+    Key::Packet,
+    Key::Attn,
+    Key::CrSel,
+    Key::ExSel,
+    Key::EraseEof,
+    Key::Play,
+    Key::Zoom,
+    Key::PA1,
+    Key::OemClear,
+];
+
 impl Key {
-    pub fn to_u32(&self) -> u32 {
+    pub fn to_u32(self) -> u32 {
         match self {
             Key::None => 0,
             Key::LeftButton => 1,
@@ -348,4 +524,236 @@ impl Key {
             Key::OemClear => 170,
         }
     }
+
+    /// Gets the name of the key, the same as its variant name.
+    pub fn get_name(self) -> &'static str {
+        match self {
+            Key::None => "None",
+            Key::LeftButton => "LeftButton",
+            Key::RightButton => "RightButton",
+            Key::Cancel => "Cancel",
+            Key::MiddleButton => "MiddleButton",
+            Key::XButton1 => "XButton1",
+            Key::XButton2 => "XButton2",
+            Key::Back => "Back",
+            Key::Tab => "Tab",
+            Key::Clear => "Clear",
+            Key::Return => "Return",
+            Key::Shift => "Shift",
+            Key::Control => "Control",
+            Key::Menu => "Menu",
+            Key::Pause => "Pause",
+            Key::Capital => "Capital",
+            Key::KanaHangelHangul => "KanaHangelHangul",
+            Key::Junja => "Junja",
+            Key::Final => "Final",
+            Key::HanjaKanji => "HanjaKanji",
+            Key::Escape => "Escape",
+            Key::Convert => "Convert",
+            Key::NonConvert => "NonConvert",
+            Key::Accept => "Accept",
+            Key::ModeChange => "ModeChange",
+            Key::Space => "Space",
+            Key::Prior => "Prior",
+            Key::Next => "Next",
+            Key::End => "End",
+            Key::Home => "Home",
+            Key::Left => "Left",
+            Key::Up => "Up",
+            Key::Right => "Right",
+            Key::Down => "Down",
+            Key::Select => "Select",
+            Key::Print => "Print",
+            Key::Execute => "Execute",
+            Key::Snapshot => "Snapshot",
+            Key::Insert => "Insert",
+            Key::Delete => "Delete",
+            Key::Help => "Help",
+            Key::Key0 => "Key0",
+            Key::Key1 => "Key1",
+            Key::Key2 => "Key2",
+            Key::Key3 => "Key3",
+            Key::Key4 => "Key4",
+            Key::Key5 => "Key5",
+            Key::Key6 => "Key6",
+            Key::Key7 => "Key7",
+            Key::Key8 => "Key8",
+            Key::Key9 => "Key9",
+            Key::A => "A",
+            Key::B => "B",
+            Key::C => "C",
+            Key::D => "D",
+            Key::E => "E",
+            Key::F => "F",
+            Key::G => "G",
+            Key::H => "H",
+            Key::I => "I",
+            Key::J => "J",
+            Key::K => "K",
+            Key::L => "L",
+            Key::M => "M",
+            Key::N => "N",
+            Key::O => "O",
+            Key::P => "P",
+            Key::Q => "Q",
+            Key::R => "R",
+            Key::S => "S",
+            Key::T => "T",
+            Key::U => "U",
+            Key::V => "V",
+            Key::W => "W",
+            Key::X => "X",
+            Key::Y => "Y",
+            Key::Z => "Z",
+            Key::LeftWin => "LeftWin",
+            Key::RightWin => "RightWin",
+            Key::Apps => "Apps",
+            Key::Sleep => "Sleep",
+            Key::NumPad0 => "NumPad0",
+            Key::NumPad1 => "NumPad1",
+            Key::NumPad2 => "NumPad2",
+            Key::NumPad3 => "NumPad3",
+            Key::NumPad4 => "NumPad4",
+            Key::NumPad5 => "NumPad5",
+            Key::NumPad6 => "NumPad6",
+            Key::NumPad7 => "NumPad7",
+            Key::NumPad8 => "NumPad8",
+            Key::NumPad9 => "NumPad9",
+            Key::Multiply => "Multiply",
+            Key::Add => "Add",
+            Key::Separator => "Separator",
+            Key::Subtract => "Subtract",
+            Key::Decimal => "Decimal",
+            Key::Divide => "Divide",
+            Key::F1 => "F1",
+            Key::F2 => "F2",
+            Key::F3 => "F3",
+            Key::F4 => "F4",
+            Key::F5 => "F5",
+            Key::F6 => "F6",
+            Key::F7 => "F7",
+            Key::F8 => "F8",
+            Key::F9 => "F9",
+            Key::F10 => "F10",
+            Key::F11 => "F11",
+            Key::F12 => "F12",
+            Key::F13 => "F13",
+            Key::F14 => "F14",
+            Key::F15 => "F15",
+            Key::F16 => "F16",
+            Key::F17 => "F17",
+            Key::F18 => "F18",
+            Key::F19 => "F19",
+            Key::F20 => "F20",
+            Key::F21 => "F21",
+            Key::F22 => "F22",
+            Key::F23 => "F23",
+            Key::F24 => "F24",
+            Key::NumLock => "NumLock",
+            Key::Scroll => "Scroll",
+            Key::LeftShift => "LeftShift",
+            Key::RightShift => "RightShift",
+            Key::LeftControl => "LeftControl",
+            Key::RightControl => "RightControl",
+            Key::LeftMenu => "LeftMenu",
+            Key::RightMenu => "RightMenu",
+            Key::BrowserBack => "BrowserBack",
+            Key::BrowserForward => "BrowserForward",
+            Key::BrowserRefresh => "BrowserRefresh",
+            Key::BrowserStop => "BrowserStop",
+            Key::BrowserSearch => "BrowserSearch",
+            Key::BrowserFavorites => "BrowserFavorites",
+            Key::BrowserHome => "BrowserHome",
+            Key::VolumeMute => "VolumeMute",
+            Key::VolumeDown => "VolumeDown",
+            Key::VolumeUp => "VolumeUp",
+            Key::MediaNextTrack => "MediaNextTrack",
+            Key::MediaPreviousTrack => "MediaPreviousTrack",
+            Key::MediaStop => "MediaStop",
+            Key::MediaPlayPause => "MediaPlayPause",
+            Key::LaunchMail => "LaunchMail",
+            Key::LaunchMediaSelect => "LaunchMediaSelect",
+            Key::LaunchApp1 => "LaunchApp1",
+            Key::LaunchApp2 => "LaunchApp2",
+            Key::Oem1 => "Oem1",
+            Key::Plus => "Plus",
+            Key::Comma => "Comma",
+            Key::Minus => "Minus",
+            Key::Period => "Period",
+            Key::Oem2 => "Oem2",
+            Key::Oem3 => "Oem3",
+            Key::Oem4 => "Oem4",
+            Key::Oem5 => "Oem5",
+            Key::Oem6 => "Oem6",
+            Key::Oem7 => "Oem7",
+            Key::Oem8 => "Oem8",
+            Key::ProcessKey => "ProcessKey",
+            Key::ImeProcess => "ImeProcess",
+            Key::Packet => "Packet",
+            Key::Attn => "Attn",
+            Key::CrSel => "CrSel",
+            Key::ExSel => "ExSel",
+            Key::EraseEof => "EraseEof",
+            Key::Play => "Play",
+            Key::Zoom => "Zoom",
+            Key::PA1 => "PA1",
+            Key::OemClear => "OemClear",
+        }
+    }
+
+    /// Gets the key with the given name, ignoring the case.
+    pub fn from_name(name: &str) -> Option<Key> {
+        ALL_KEYS
+            .iter()
+            .find(|key| key.get_name().eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
+    /// Checks if the key is shift, control or menu (alt), on either side.
+    pub fn is_modifier(self) -> bool {
+        matches!(
+            self,
+            Key::Shift
+                | Key::Control
+                | Key::Menu
+                | Key::LeftShift
+                | Key::RightShift
+                | Key::LeftControl
+                | Key::RightControl
+                | Key::LeftMenu
+                | Key::RightMenu
+        )
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        formatter.write_str(self.get_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::input::key::{Key, ALL_KEYS};
+
+    #[test]
+    fn from_name_ignores_the_case() {
+        assert_eq!(Key::from_name("Escape"), Some(Key::Escape));
+        assert_eq!(Key::from_name("escape"), Some(Key::Escape));
+        assert_eq!(Key::from_name("NUMPAD8"), Some(Key::NumPad8));
+    }
+
+    #[test]
+    fn from_name_rejects_unknown_names() {
+        assert_eq!(Key::from_name("Esc"), None);
+        assert_eq!(Key::from_name(""), None);
+        assert_eq!(Key::from_name("MouseLeft"), None);
+    }
+
+    #[test]
+    fn every_name_round_trips() {
+        for key in ALL_KEYS.iter() {
+            assert_eq!(Key::from_name(key.get_name()), Some(*key));
+        }
+    }
 }
@@ -0,0 +1,100 @@
+use core::input::key::Key;
+use core::input::modifiers::Modifiers;
+use std::fmt::{Display, Formatter, Result as FormatResult};
+
+/// A key combined with an exact set of modifiers, like `Ctrl+Shift+S`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct KeyChord {
+    pub key: Key,
+    pub modifiers: Modifiers,
+}
+
+#[allow(dead_code)]
+impl KeyChord {
+    pub fn new(key: Key, modifiers: Modifiers) -> KeyChord {
+        KeyChord { key, modifiers }
+    }
+
+    /// Creates a chord of a key without modifiers.
+    pub fn from_key(key: Key) -> KeyChord {
+        KeyChord::new(key, Modifiers::NONE)
+    }
+
+    /// Parses a chord like `Ctrl+Shift+S`: the modifiers (`Ctrl`, `Shift`
+    /// and `Alt`) followed by the key name, separated by `+`.
+    pub fn parse(text: &str) -> Option<KeyChord> {
+        let mut parts: Vec<&str> = text.split('+').map(|part| part.trim()).collect();
+        let key = Key::from_name(parts.pop()?)?;
+        let mut modifiers = Modifiers::NONE;
+
+        for part in parts {
+            modifiers |= match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => Modifiers::CONTROL,
+                "shift" => Modifiers::SHIFT,
+                "alt" => Modifiers::ALT,
+                _ => return None,
+            };
+        }
+
+        Some(KeyChord::new(key, modifiers))
+    }
+}
+
+impl Display for KeyChord {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        for &(modifier, name) in [
+            (Modifiers::CONTROL, "Ctrl+"),
+            (Modifiers::SHIFT, "Shift+"),
+            (Modifiers::ALT, "Alt+"),
+        ]
+        .iter()
+        {
+            if self.modifiers.contains(modifier) {
+                formatter.write_str(name)?;
+            }
+        }
+
+        formatter.write_str(self.key.get_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::input::key::Key;
+    use core::input::key_chord::KeyChord;
+    use core::input::modifiers::Modifiers;
+
+    #[test]
+    fn parses_a_key_with_modifiers() {
+        assert_eq!(KeyChord::parse("S"), Some(KeyChord::from_key(Key::S)));
+        assert_eq!(
+            KeyChord::parse("Ctrl+Shift+S"),
+            Some(KeyChord::new(Key::S, Modifiers::CONTROL | Modifiers::SHIFT))
+        );
+        assert_eq!(
+            KeyChord::parse(" alt + control + Up "),
+            Some(KeyChord::new(Key::Up, Modifiers::ALT | Modifiers::CONTROL))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_chords() {
+        assert_eq!(KeyChord::parse(""), None);
+        assert_eq!(KeyChord::parse("Ctrl+"), None);
+        assert_eq!(KeyChord::parse("Ctrl"), None);
+        assert_eq!(KeyChord::parse("Super+S"), None);
+        assert_eq!(KeyChord::parse("S+Ctrl"), None);
+        assert_eq!(KeyChord::parse("Ctrl+Unknown"), None);
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let chord = KeyChord::new(
+            Key::F5,
+            Modifiers::ALT | Modifiers::SHIFT | Modifiers::CONTROL,
+        );
+
+        assert_eq!(chord.to_string(), "Ctrl+Shift+Alt+F5");
+        assert_eq!(KeyChord::parse(&chord.to_string()), Some(chord));
+    }
+}
@@ -1,18 +1,57 @@
 use core::events::event::{KeyboardEvent, KeyboardEventType};
 use core::input::key::Key;
+use core::input::key_chord::KeyChord;
+use core::input::modifiers::Modifiers;
+use std::time::{Duration, Instant};
 
+const KEY_COUNT: usize = 175;
+
+/// The state of every key, with the changes of the current frame. A frame
+/// starts on every `Application::listen_events` call, so the presses and
+/// releases are kept until the next one.
 pub struct KeyboardState {
-    pub keys: [bool; 175],
+    pub keys: [bool; KEY_COUNT],
+    pressed: [bool; KEY_COUNT],
+    released: [bool; KEY_COUNT],
+    pressed_modifiers: [Modifiers; KEY_COUNT],
+    pressed_at: [Option<Instant>; KEY_COUNT],
 }
 
+#[allow(dead_code)]
 impl KeyboardState {
     pub fn new() -> KeyboardState {
-        KeyboardState { keys: [false; 175] }
+        KeyboardState {
+            keys: [false; KEY_COUNT],
+            pressed: [false; KEY_COUNT],
+            released: [false; KEY_COUNT],
+            pressed_modifiers: [Modifiers::NONE; KEY_COUNT],
+            pressed_at: [None; KEY_COUNT],
+        }
+    }
+
+    /// Forgets the presses and releases of the previous frame.
+    pub fn begin_frame(&mut self) {
+        self.pressed = [false; KEY_COUNT];
+        self.released = [false; KEY_COUNT];
     }
 
     pub fn update_from_event(&mut self, keyboard: KeyboardEvent) {
-        self.keys[keyboard.key.to_u32() as usize] =
-            keyboard.event_type == KeyboardEventType::KeyDown;
+        let index = keyboard.key.to_u32() as usize;
+        let down = keyboard.event_type == KeyboardEventType::KeyDown;
+
+        // the key repeats aren't new presses.
+        if down && !self.keys[index] {
+            self.pressed[index] = true;
+            self.pressed_modifiers[index] = Modifiers::from_event(&keyboard);
+            self.pressed_at[index] = Some(Instant::now());
+        }
+
+        if !down {
+            self.released[index] = self.released[index] || self.keys[index];
+            self.pressed_at[index] = None;
+        }
+
+        self.keys[index] = down;
         self.keys[Key::LeftShift.to_u32() as usize] = keyboard.left_shift;
         self.keys[Key::LeftControl.to_u32() as usize] = keyboard.left_control;
         self.keys[Key::LeftMenu.to_u32() as usize] = keyboard.left_menu;
@@ -20,4 +59,208 @@ impl KeyboardState {
         self.keys[Key::RightControl.to_u32() as usize] = keyboard.right_control;
         self.keys[Key::RightMenu.to_u32() as usize] = keyboard.right_menu;
     }
+
+    /// Checks if the key is held down.
+    #[inline]
+    pub fn is_down(&self, key: Key) -> bool {
+        self.keys[key.to_u32() as usize]
+    }
+
+    /// Checks if the key went down during the current frame. Terminals
+    /// without key release reports press and release the key at once, so
+    /// the press is reported even when the key isn't down anymore.
+    #[inline]
+    pub fn is_pressed(&self, key: Key) -> bool {
+        self.pressed[key.to_u32() as usize]
+    }
+
+    /// Checks if the key went up during the current frame.
+    #[inline]
+    pub fn is_released(&self, key: Key) -> bool {
+        self.released[key.to_u32() as usize]
+    }
+
+    /// Gets for how long the key has been held down, zero when it isn't.
+    pub fn held_duration(&self, key: Key) -> Duration {
+        match self.pressed_at[key.to_u32() as usize] {
+            Some(pressed_at) if self.is_down(key) => pressed_at.elapsed(),
+            _ => Duration::from_secs(0),
+        }
+    }
+
+    /// Gets the modifiers currently held.
+    pub fn get_modifiers(&self) -> Modifiers {
+        let mut modifiers = Modifiers::NONE;
+
+        if self.is_down(Key::LeftShift) || self.is_down(Key::RightShift) {
+            modifiers |= Modifiers::SHIFT;
+        }
+
+        if self.is_down(Key::LeftControl) || self.is_down(Key::RightControl) {
+            modifiers |= Modifiers::CONTROL;
+        }
+
+        if self.is_down(Key::LeftMenu) || self.is_down(Key::RightMenu) {
+            modifiers |= Modifiers::ALT;
+        }
+
+        modifiers
+    }
+
+    /// Checks if the chord key went down during the current frame while
+    /// exactly the chord modifiers were held.
+    pub fn is_chord_pressed(&self, chord: KeyChord) -> bool {
+        let index = chord.key.to_u32() as usize;
+        self.pressed[index] && self.pressed_modifiers[index] == chord.modifiers
+    }
+
+    /// Checks if the chord key is held down with exactly the chord modifiers.
+    pub fn is_chord_down(&self, chord: KeyChord) -> bool {
+        self.is_down(chord.key) && self.get_modifiers() == chord.modifiers
+    }
+}
+
+impl Default for KeyboardState {
+    fn default() -> KeyboardState {
+        KeyboardState::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::events::event::{KeyboardEvent, KeyboardEventType};
+    use core::input::key::Key;
+    use core::input::key_chord::KeyChord;
+    use core::input::keyboard_state::KeyboardState;
+    use core::input::modifiers::Modifiers;
+
+    fn key_event(key: Key, down: bool, left_control: bool, left_shift: bool) -> KeyboardEvent {
+        KeyboardEvent {
+            event_type: if down {
+                KeyboardEventType::KeyDown
+            } else {
+                KeyboardEventType::KeyUp
+            },
+            key,
+            key_code: 0,
+            character: '\0',
+            left_control,
+            left_shift,
+            left_menu: false,
+            right_control: false,
+            right_shift: false,
+            right_menu: false,
+        }
+    }
+
+    fn press(state: &mut KeyboardState, key: Key) {
+        state.update_from_event(key_event(key, true, false, false));
+    }
+
+    fn release(state: &mut KeyboardState, key: Key) {
+        state.update_from_event(key_event(key, false, false, false));
+    }
+
+    #[test]
+    fn press_is_reported_only_in_its_frame() {
+        let mut state = KeyboardState::new();
+        state.begin_frame();
+        press(&mut state, Key::A);
+
+        assert!(state.is_pressed(Key::A));
+        assert!(state.is_down(Key::A));
+        assert!(!state.is_released(Key::A));
+
+        state.begin_frame();
+        assert!(!state.is_pressed(Key::A));
+        assert!(state.is_down(Key::A));
+    }
+
+    #[test]
+    fn repeats_are_not_new_presses() {
+        let mut state = KeyboardState::new();
+        state.begin_frame();
+        press(&mut state, Key::A);
+
+        state.begin_frame();
+        press(&mut state, Key::A);
+        press(&mut state, Key::A);
+
+        assert!(!state.is_pressed(Key::A));
+        assert!(state.is_down(Key::A));
+    }
+
+    #[test]
+    fn release_is_reported_only_in_its_frame() {
+        let mut state = KeyboardState::new();
+        state.begin_frame();
+        press(&mut state, Key::A);
+
+        state.begin_frame();
+        release(&mut state, Key::A);
+        assert!(state.is_released(Key::A));
+        assert!(!state.is_down(Key::A));
+
+        state.begin_frame();
+        assert!(!state.is_released(Key::A));
+    }
+
+    #[test]
+    fn release_of_a_key_that_was_up_is_ignored() {
+        let mut state = KeyboardState::new();
+        state.begin_frame();
+        release(&mut state, Key::A);
+
+        assert!(!state.is_released(Key::A));
+    }
+
+    #[test]
+    fn press_and_release_in_one_frame_report_both() {
+        let mut state = KeyboardState::new();
+        state.begin_frame();
+        press(&mut state, Key::A);
+        release(&mut state, Key::A);
+
+        assert!(state.is_pressed(Key::A));
+        assert!(state.is_released(Key::A));
+        assert!(!state.is_down(Key::A));
+    }
+
+    #[test]
+    fn chord_needs_exactly_its_modifiers() {
+        let save = KeyChord::new(Key::S, Modifiers::CONTROL);
+        let mut state = KeyboardState::new();
+        state.begin_frame();
+        state.update_from_event(key_event(Key::S, true, true, false));
+
+        assert!(state.is_chord_pressed(save));
+        assert!(state.is_chord_down(save));
+        assert!(!state.is_chord_pressed(KeyChord::from_key(Key::S)));
+
+        let mut state = KeyboardState::new();
+        state.begin_frame();
+        state.update_from_event(key_event(Key::S, true, true, true));
+
+        assert!(!state.is_chord_pressed(save));
+        assert!(!state.is_chord_down(save));
+        assert!(
+            state.is_chord_pressed(KeyChord::new(Key::S, Modifiers::CONTROL | Modifiers::SHIFT))
+        );
+    }
+
+    #[test]
+    fn chord_press_keeps_the_modifiers_of_the_press() {
+        let save = KeyChord::new(Key::S, Modifiers::CONTROL);
+        let mut state = KeyboardState::new();
+        state.begin_frame();
+        state.update_from_event(key_event(Key::S, true, true, false));
+
+        // releasing control in the same frame keeps the press of the chord,
+        // but the chord isn't held anymore.
+        state.update_from_event(key_event(Key::LeftControl, false, false, false));
+
+        assert!(state.is_chord_pressed(save));
+        assert!(!state.is_chord_down(save));
+        assert_eq!(state.get_modifiers(), Modifiers::NONE);
+    }
 }
@@ -1,3 +1,10 @@
+pub mod binding;
+pub mod drag;
+pub mod drag_phase;
+pub mod input_map;
 pub mod key;
+pub mod key_chord;
 pub mod keyboard_state;
+pub mod modifiers;
+pub mod mouse_button;
 pub mod mouse_state;
@@ -0,0 +1,61 @@
+use core::events::event::KeyboardEvent;
+use std::ops::{BitOr, BitOrAssign};
+
+/// Set of modifier keys, without telling apart the left and right keys.
+/// Modifiers are combined with `|`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers(u8);
+
+#[allow(dead_code)]
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers(0);
+    pub const SHIFT: Modifiers = Modifiers(1);
+    pub const CONTROL: Modifiers = Modifiers(1 << 1);
+    pub const ALT: Modifiers = Modifiers(1 << 2);
+
+    /// Gets the modifiers held when a keyboard event happened.
+    pub fn from_event(keyboard: &KeyboardEvent) -> Modifiers {
+        let mut modifiers = Modifiers::NONE;
+
+        if keyboard.left_shift || keyboard.right_shift {
+            modifiers |= Modifiers::SHIFT;
+        }
+
+        if keyboard.left_control || keyboard.right_control {
+            modifiers |= Modifiers::CONTROL;
+        }
+
+        if keyboard.left_menu || keyboard.right_menu {
+            modifiers |= Modifiers::ALT;
+        }
+
+        modifiers
+    }
+
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Checks whether every modifier of the given set is held.
+    pub fn contains(self, modifiers: Modifiers) -> bool {
+        self.0 & modifiers.0 == modifiers.0
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, modifiers: Modifiers) -> Modifiers {
+        Modifiers(self.0 | modifiers.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, modifiers: Modifiers) {
+        self.0 |= modifiers.0;
+    }
+}
@@ -0,0 +1,62 @@
+use core::events::event::MouseEvent;
+
+/// Enumerates the mouse buttons.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+    Extra1,
+    Extra2,
+    Extra3,
+    Extra4,
+}
+
+/// Every mouse button.
+pub const ALL_MOUSE_BUTTONS: [MouseButton; 7] = [
+    MouseButton::Left,
+    MouseButton::Middle,
+    MouseButton::Right,
+    MouseButton::Extra1,
+    MouseButton::Extra2,
+    MouseButton::Extra3,
+    MouseButton::Extra4,
+];
+
+#[allow(dead_code)]
+impl MouseButton {
+    /// Gets the name of the button, prefixed with `Mouse` to tell it apart
+    /// from the key names.
+    pub fn get_name(self) -> &'static str {
+        match self {
+            MouseButton::Left => "MouseLeft",
+            MouseButton::Middle => "MouseMiddle",
+            MouseButton::Right => "MouseRight",
+            MouseButton::Extra1 => "MouseExtra1",
+            MouseButton::Extra2 => "MouseExtra2",
+            MouseButton::Extra3 => "MouseExtra3",
+            MouseButton::Extra4 => "MouseExtra4",
+        }
+    }
+
+    /// Gets the button with the given name, ignoring the case.
+    pub fn from_name(name: &str) -> Option<MouseButton> {
+        ALL_MOUSE_BUTTONS
+            .iter()
+            .find(|button| button.get_name().eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
+    /// Checks if the button is held in a mouse event.
+    pub fn is_down_in(self, mouse: &MouseEvent) -> bool {
+        match self {
+            MouseButton::Left => mouse.left_button,
+            MouseButton::Middle => mouse.middle_button,
+            MouseButton::Right => mouse.right_button,
+            MouseButton::Extra1 => mouse.extra_button_1,
+            MouseButton::Extra2 => mouse.extra_button_2,
+            MouseButton::Extra3 => mouse.extra_button_3,
+            MouseButton::Extra4 => mouse.extra_button_4,
+        }
+    }
+}
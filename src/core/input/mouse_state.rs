@@ -1,6 +1,16 @@
 use core::drawing::point_2d::Point2d;
-use core::events::event::MouseEvent;
+use core::events::event::{MouseEvent, MouseEventType};
+use core::input::drag::Drag;
+use core::input::drag_phase::DragPhase;
+use core::input::mouse_button::{MouseButton, ALL_MOUSE_BUTTONS};
+use std::time::{Duration, Instant};
 
+const BUTTON_COUNT: usize = 7;
+
+/// The state of the mouse buttons, with the changes of the current frame.
+/// Like the keyboard state, a frame starts on every
+/// `Application::listen_events` call. Clicks and drags are detected here,
+/// so they behave the same on every backend.
 pub struct MouseState {
     pub left_button: bool,
     pub middle_button: bool,
@@ -10,8 +20,16 @@ pub struct MouseState {
     pub extra_button_3: bool,
     pub extra_button_4: bool,
     pub position: Point2d,
+    pressed: [bool; BUTTON_COUNT],
+    released: [bool; BUTTON_COUNT],
+    clicks: [u32; BUTTON_COUNT],
+    press_origin: [Point2d; BUTTON_COUNT],
+    last_click: Option<(MouseButton, Point2d, Instant, u32)>,
+    click_interval: Duration,
+    drag: Option<Drag>,
 }
 
+#[allow(dead_code)]
 impl MouseState {
     pub fn new() -> MouseState {
         MouseState {
@@ -23,10 +41,54 @@ impl MouseState {
             extra_button_3: false,
             extra_button_4: false,
             position: Point2d::empty(),
+            pressed: [false; BUTTON_COUNT],
+            released: [false; BUTTON_COUNT],
+            clicks: [0; BUTTON_COUNT],
+            press_origin: [Point2d::empty(); BUTTON_COUNT],
+            last_click: None,
+            click_interval: Duration::from_millis(500),
+            drag: None,
         }
     }
 
-    pub fn update_from_event(&mut self, mouse: MouseEvent) {
+    /// Sets the longest time between the presses of a double or triple click.
+    pub fn set_click_interval(&mut self, click_interval: Duration) {
+        self.click_interval = click_interval;
+    }
+
+    /// Forgets the presses, releases, clicks and drag ends of the previous frame.
+    pub fn begin_frame(&mut self) {
+        self.pressed = [false; BUTTON_COUNT];
+        self.released = [false; BUTTON_COUNT];
+        self.clicks = [0; BUTTON_COUNT];
+
+        self.drag = match self.drag {
+            Some(drag) if drag.phase == DragPhase::Ended => None,
+            Some(drag) => Some(Drag {
+                phase: DragPhase::Moved,
+                ..drag
+            }),
+            None => None,
+        };
+    }
+
+    /// Updates the state from a mouse event. The button presses are
+    /// reported as `Click` or, when they repeat a click within the click
+    /// interval on the same cell, `DoubleClick`.
+    pub fn update_from_event(&mut self, mouse: &mut MouseEvent) {
+        self.position = mouse.position;
+
+        for (index, button) in ALL_MOUSE_BUTTONS.iter().enumerate() {
+            let was_down = self.is_down(*button);
+            let down = button.is_down_in(mouse);
+
+            if down && !was_down {
+                self.press(index, *button, mouse);
+            } else if !down && was_down {
+                self.release(index, *button);
+            }
+        }
+
         self.left_button = mouse.left_button;
         self.middle_button = mouse.middle_button;
         self.right_button = mouse.right_button;
@@ -34,6 +96,269 @@ impl MouseState {
         self.extra_button_2 = mouse.extra_button_2;
         self.extra_button_3 = mouse.extra_button_3;
         self.extra_button_4 = mouse.extra_button_4;
-        self.position = mouse.position;
+
+        self.update_drag(mouse.position);
+    }
+
+    /// Checks if the button is held down.
+    pub fn is_down(&self, button: MouseButton) -> bool {
+        match button {
+            MouseButton::Left => self.left_button,
+            MouseButton::Middle => self.middle_button,
+            MouseButton::Right => self.right_button,
+            MouseButton::Extra1 => self.extra_button_1,
+            MouseButton::Extra2 => self.extra_button_2,
+            MouseButton::Extra3 => self.extra_button_3,
+            MouseButton::Extra4 => self.extra_button_4,
+        }
+    }
+
+    /// Checks if the button went down during the current frame.
+    #[inline]
+    pub fn is_pressed(&self, button: MouseButton) -> bool {
+        self.pressed[button as usize]
+    }
+
+    /// Checks if the button went up during the current frame.
+    #[inline]
+    pub fn is_released(&self, button: MouseButton) -> bool {
+        self.released[button as usize]
+    }
+
+    /// Gets the amount of consecutive clicks of the press of the current
+    /// frame: 1 for a single click, 2 for a double click and so on. Gets 0
+    /// when the button wasn't pressed during the frame.
+    #[inline]
+    pub fn get_clicks(&self, button: MouseButton) -> u32 {
+        self.clicks[button as usize]
+    }
+
+    #[inline]
+    pub fn is_double_clicked(&self, button: MouseButton) -> bool {
+        self.get_clicks(button) == 2
+    }
+
+    #[inline]
+    pub fn is_triple_clicked(&self, button: MouseButton) -> bool {
+        self.get_clicks(button) == 3
+    }
+
+    /// Gets the drag of the current frame, including one that ended in it.
+    #[inline]
+    pub fn get_drag(&self) -> Option<Drag> {
+        self.drag
+    }
+
+    fn press(&mut self, index: usize, button: MouseButton, mouse: &mut MouseEvent) {
+        let now = Instant::now();
+        let position = mouse.position;
+
+        let clicks = match self.last_click {
+            Some((last_button, last_position, last_time, clicks))
+                if last_button == button
+                    && last_position.equal_to(position)
+                    && now - last_time <= self.click_interval =>
+            {
+                clicks + 1
+            }
+            _ => 1,
+        };
+
+        self.pressed[index] = true;
+        self.clicks[index] = clicks;
+        self.press_origin[index] = position;
+        self.last_click = Some((button, position, now, clicks));
+
+        // the backends disagree on double clicks, so the count decides.
+        if mouse.event_type == MouseEventType::Click
+            || mouse.event_type == MouseEventType::DoubleClick
+        {
+            mouse.event_type = if clicks > 1 {
+                MouseEventType::DoubleClick
+            } else {
+                MouseEventType::Click
+            };
+        }
+    }
+
+    fn release(&mut self, index: usize, button: MouseButton) {
+        self.released[index] = true;
+
+        if let Some(drag) = self.drag.as_mut() {
+            if drag.button == button {
+                drag.phase = DragPhase::Ended;
+            }
+        }
+    }
+
+    /// Starts a drag when the mouse leaves the cell where a button was
+    /// pressed, and follows the mouse until the button is released.
+    fn update_drag(&mut self, position: Point2d) {
+        match self.drag.as_mut() {
+            Some(drag) if drag.phase != DragPhase::Ended => {
+                drag.position = position;
+                return;
+            }
+            Some(_) => return,
+            None => (),
+        }
+
+        for (index, button) in ALL_MOUSE_BUTTONS.iter().enumerate() {
+            if self.is_down(*button) && !self.press_origin[index].equal_to(position) {
+                // a drag isn't a click, so the next press starts a new count.
+                self.last_click = None;
+                self.drag = Some(Drag {
+                    button: *button,
+                    phase: DragPhase::Started,
+                    origin: self.press_origin[index],
+                    position,
+                });
+                return;
+            }
+        }
+    }
+}
+
+impl Default for MouseState {
+    fn default() -> MouseState {
+        MouseState::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::point_2d::Point2d;
+    use core::events::event::{MouseEvent, MouseEventType};
+    use core::input::drag_phase::DragPhase;
+    use core::input::mouse_button::MouseButton;
+    use core::input::mouse_state::MouseState;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    /// Starts a frame with a single mouse event and gets its type, as
+    /// rewritten by the state.
+    fn frame(state: &mut MouseState, x: i32, left_button: bool) -> MouseEventType {
+        let mut mouse = MouseEvent {
+            event_type: if left_button && !state.left_button {
+                MouseEventType::Click
+            } else {
+                MouseEventType::MouseMove
+            },
+            left_button,
+            middle_button: false,
+            right_button: false,
+            extra_button_1: false,
+            extra_button_2: false,
+            extra_button_3: false,
+            extra_button_4: false,
+            position: Point2d::new(x, 0),
+        };
+
+        state.begin_frame();
+        state.update_from_event(&mut mouse);
+        mouse.event_type
+    }
+
+    #[test]
+    fn press_and_release_are_reported_in_their_frames() {
+        let mut state = MouseState::new();
+
+        frame(&mut state, 1, true);
+        assert!(state.is_pressed(MouseButton::Left));
+        assert!(state.is_down(MouseButton::Left));
+
+        frame(&mut state, 1, true);
+        assert!(!state.is_pressed(MouseButton::Left));
+        assert!(state.is_down(MouseButton::Left));
+
+        frame(&mut state, 1, false);
+        assert!(state.is_released(MouseButton::Left));
+        assert!(!state.is_down(MouseButton::Left));
+
+        frame(&mut state, 1, false);
+        assert!(!state.is_released(MouseButton::Left));
+    }
+
+    #[test]
+    fn repeated_presses_on_a_cell_count_the_clicks() {
+        let mut state = MouseState::new();
+
+        assert_eq!(frame(&mut state, 1, true), MouseEventType::Click);
+        assert_eq!(state.get_clicks(MouseButton::Left), 1);
+        frame(&mut state, 1, false);
+        assert_eq!(state.get_clicks(MouseButton::Left), 0);
+
+        assert_eq!(frame(&mut state, 1, true), MouseEventType::DoubleClick);
+        assert!(state.is_double_clicked(MouseButton::Left));
+        frame(&mut state, 1, false);
+
+        frame(&mut state, 1, true);
+        assert!(state.is_triple_clicked(MouseButton::Left));
+    }
+
+    #[test]
+    fn clicks_on_another_cell_or_too_late_start_again() {
+        let mut state = MouseState::new();
+        state.set_click_interval(Duration::from_millis(10));
+
+        frame(&mut state, 1, true);
+        frame(&mut state, 1, false);
+        assert_eq!(frame(&mut state, 2, true), MouseEventType::Click);
+        frame(&mut state, 2, false);
+
+        sleep(Duration::from_millis(20));
+        assert_eq!(frame(&mut state, 2, true), MouseEventType::Click);
+        assert_eq!(state.get_clicks(MouseButton::Left), 1);
+    }
+
+    #[test]
+    fn drag_starts_when_the_mouse_leaves_the_pressed_cell() {
+        let mut state = MouseState::new();
+
+        frame(&mut state, 1, true);
+        frame(&mut state, 1, true);
+        assert!(state.get_drag().is_none());
+
+        frame(&mut state, 3, true);
+        let drag = state.get_drag().unwrap();
+        assert_eq!(drag.button, MouseButton::Left);
+        assert_eq!(drag.phase, DragPhase::Started);
+        assert_eq!(drag.origin, Point2d::new(1, 0));
+        assert_eq!(drag.position, Point2d::new(3, 0));
+
+        frame(&mut state, 4, true);
+        let drag = state.get_drag().unwrap();
+        assert_eq!(drag.phase, DragPhase::Moved);
+        assert_eq!(drag.position, Point2d::new(4, 0));
+
+        frame(&mut state, 4, false);
+        assert_eq!(state.get_drag().unwrap().phase, DragPhase::Ended);
+
+        frame(&mut state, 4, false);
+        assert!(state.get_drag().is_none());
+    }
+
+    #[test]
+    fn release_on_the_pressed_cell_is_a_click_not_a_drag() {
+        let mut state = MouseState::new();
+
+        frame(&mut state, 1, true);
+        frame(&mut state, 1, false);
+
+        assert!(state.get_drag().is_none());
+        assert!(state.is_released(MouseButton::Left));
+    }
+
+    #[test]
+    fn drag_breaks_the_click_count() {
+        let mut state = MouseState::new();
+
+        frame(&mut state, 1, true);
+        frame(&mut state, 3, true);
+        frame(&mut state, 1, true);
+        frame(&mut state, 1, false);
+
+        assert_eq!(frame(&mut state, 1, true), MouseEventType::Click);
+        assert_eq!(state.get_clicks(MouseButton::Left), 1);
     }
 }
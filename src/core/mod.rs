@@ -1,11 +1,15 @@
+use core::error::Error;
 use std::result;
 
-pub type Result<T> = result::Result<T, &'static str>;
+pub type Result<T> = result::Result<T, Error>;
 
 pub mod application;
 pub mod drawing;
+pub mod error;
 pub mod events;
+pub mod game_loop;
 pub mod input;
 pub mod mouse;
+pub mod scenes;
 pub mod terminal;
 pub mod window;
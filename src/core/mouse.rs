@@ -1,4 +1,7 @@
 use core::drawing::point_2d::Point2d;
+use core::drawing::size_2d::Size2d;
+use core::drawing::sub_cell_point::SubCellPoint;
+use core::error::Error;
 use core::Result;
 
 pub trait Mouse {
@@ -13,4 +16,79 @@ pub trait Mouse {
 
     /// Shows or hides the mouse cursor.
     fn show_cursor(&self, visible: bool) -> Result<()>;
+
+    /// Gets the client mouse position in character cells, with the fraction
+    /// inside the cell. The char size is the one from `Terminal::get_char_size`.
+    fn get_sub_cell_position(&self, char_size: Size2d) -> Result<SubCellPoint> {
+        SubCellPoint::from_pixels(self.get_client_position()?, char_size).ok_or(Error::InvalidSize)
+    }
+
+    /// Gets the client mouse position in character cells.
+    fn get_cell_position(&self, char_size: Size2d) -> Result<Point2d> {
+        Ok(self.get_sub_cell_position(char_size)?.get_cell())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::size_2d::Size2d;
+    use core::error::Error;
+    use core::mouse::Mouse;
+    use core::Result;
+
+    /// A mouse fixed at a client position in pixels.
+    struct FixedMouse {
+        position: Point2d,
+    }
+
+    impl Mouse for FixedMouse {
+        fn get_absolute_position(&self) -> Result<Point2d> {
+            Ok(self.position)
+        }
+
+        fn get_client_position(&self) -> Result<Point2d> {
+            Ok(self.position)
+        }
+
+        fn set_position(&self, _position: Point2d) -> Result<()> {
+            Ok(())
+        }
+
+        fn show_cursor(&self, _visible: bool) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn client_pixels_are_converted_to_cells() {
+        let mouse = FixedMouse {
+            position: Point2d::new(25, 40),
+        };
+        let char_size = Size2d::new(10, 16);
+
+        let position = mouse.get_sub_cell_position(char_size).unwrap();
+        assert_eq!(position.x, 2.5);
+        assert_eq!(position.y, 2.5);
+        assert_eq!(
+            mouse.get_cell_position(char_size).unwrap(),
+            Point2d::new(2, 2)
+        );
+    }
+
+    #[test]
+    fn zero_char_size_is_an_invalid_size() {
+        let mouse = FixedMouse {
+            position: Point2d::new(25, 40),
+        };
+
+        match mouse.get_cell_position(Size2d::new(0, 0)) {
+            Err(Error::InvalidSize) => (),
+            result => panic!("unexpected result {:?}", result),
+        }
+        match mouse.get_sub_cell_position(Size2d::new(10, 0)) {
+            Err(Error::InvalidSize) => (),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
 }
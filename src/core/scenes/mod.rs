@@ -0,0 +1,3 @@
+pub mod scene;
+pub mod scene_stack;
+pub mod transition;
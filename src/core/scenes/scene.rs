@@ -0,0 +1,38 @@
+use core::application::Application;
+use core::drawing::cell_buffer::CellBuffer;
+use core::events::event::Event;
+use core::scenes::transition::Transition;
+use std::time::Duration;
+
+/// A screen of the game, like a menu, the map or an inventory overlay.
+pub trait Scene {
+    /// Handles an event, requesting a change to the scene stack. The
+    /// application gives access to the input state, like in `Game`.
+    fn handle_event(&mut self, event: &Event, application: &dyn Application) -> Transition;
+
+    /// Advances the scene state, requesting a change to the scene stack.
+    fn update(&mut self, _dt: Duration, _application: &dyn Application) -> Transition {
+        Transition::None
+    }
+
+    /// Draws the scene onto the shared buffer.
+    fn draw(&mut self, buffer: &mut CellBuffer);
+
+    /// Transparent scenes are drawn over the scene below them, instead of
+    /// hiding it.
+    fn is_transparent(&self) -> bool {
+        false
+    }
+
+    /// Modal scenes stop the events and the updates from reaching the
+    /// scenes below them.
+    fn is_modal(&self) -> bool {
+        true
+    }
+
+    /// Called when the scene is put on the stack.
+    fn on_enter(&mut self) {}
+
+    /// Called when the scene is removed from the stack.
+    fn on_exit(&mut self) {}
+}
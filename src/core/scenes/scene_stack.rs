@@ -0,0 +1,269 @@
+use core::application::Application;
+use core::drawing::cell_buffer::CellBuffer;
+use core::events::event::Event;
+use core::game_loop::game::Game;
+use core::scenes::scene::Scene;
+use core::scenes::transition::Transition;
+use std::time::Duration;
+
+/// A stack of scenes run as a game. Events and updates go from the top scene
+/// down to the first modal one, and the scenes are drawn from the first
+/// opaque one up to the top. The game quits when the stack is empty.
+pub struct SceneStack {
+    scenes: Vec<Box<dyn Scene>>,
+}
+
+#[allow(dead_code)]
+impl SceneStack {
+    pub fn new() -> SceneStack {
+        SceneStack { scenes: Vec::new() }
+    }
+
+    pub fn push(&mut self, mut scene: Box<dyn Scene>) {
+        scene.on_enter();
+        self.scenes.push(scene);
+    }
+
+    pub fn pop(&mut self) -> Option<Box<dyn Scene>> {
+        let mut scene = self.scenes.pop()?;
+        scene.on_exit();
+        Some(scene)
+    }
+
+    pub fn replace(&mut self, scene: Box<dyn Scene>) -> Option<Box<dyn Scene>> {
+        let previous = self.pop();
+        self.push(scene);
+        previous
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    /// Applies a transition as if the top scene requested it. Returns true
+    /// when the stack changed.
+    pub fn apply(&mut self, transition: Transition) -> bool {
+        if self.scenes.is_empty() {
+            return match transition {
+                Transition::Push(scene) | Transition::Replace(scene) => {
+                    self.push(scene);
+                    true
+                }
+                _ => false,
+            };
+        }
+
+        let top = self.scenes.len() - 1;
+        self.apply_at(top, transition)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    /// Applies a transition requested by the scene at an index, so a scene
+    /// below a non modal one pops or replaces itself, and pushes the new
+    /// scene right above itself.
+    fn apply_at(&mut self, index: usize, transition: Transition) -> bool {
+        match transition {
+            Transition::None => return false,
+            Transition::Push(mut scene) => {
+                scene.on_enter();
+                self.scenes.insert(index + 1, scene);
+            }
+            Transition::Pop => {
+                let mut scene = self.scenes.remove(index);
+                scene.on_exit();
+            }
+            Transition::Replace(mut scene) => {
+                self.scenes[index].on_exit();
+                scene.on_enter();
+                self.scenes[index] = scene;
+            }
+            Transition::Quit => self.clear(),
+        }
+
+        true
+    }
+
+    /// Gets the amount of scenes, from the top, that receive the input.
+    fn get_input_depth(&self) -> usize {
+        match self.scenes.iter().rev().position(|scene| scene.is_modal()) {
+            Some(position) => position + 1,
+            None => self.scenes.len(),
+        }
+    }
+
+    /// Gets the index of the lowest scene that is visible.
+    fn get_first_visible(&self) -> usize {
+        match self
+            .scenes
+            .iter()
+            .rev()
+            .position(|scene| !scene.is_transparent())
+        {
+            Some(position) => self.scenes.len() - 1 - position,
+            None => 0,
+        }
+    }
+}
+
+impl Default for SceneStack {
+    fn default() -> SceneStack {
+        SceneStack::new()
+    }
+}
+
+impl Game for SceneStack {
+    fn handle_event(&mut self, event: Event, application: &dyn Application) {
+        let top = self.scenes.len();
+
+        // the event stops as soon as a scene changes the stack.
+        for index in (top - self.get_input_depth()..top).rev() {
+            let transition = self.scenes[index].handle_event(&event, application);
+
+            if self.apply_at(index, transition) {
+                break;
+            }
+        }
+    }
+
+    fn update(&mut self, dt: Duration, application: &dyn Application) {
+        let top = self.scenes.len();
+
+        for index in (top - self.get_input_depth()..top).rev() {
+            let transition = self.scenes[index].update(dt, application);
+
+            if self.apply_at(index, transition) {
+                break;
+            }
+        }
+    }
+
+    fn draw(&mut self, buffer: &mut CellBuffer) {
+        let first_visible = self.get_first_visible();
+
+        for scene in self.scenes[first_visible..].iter_mut() {
+            scene.draw(buffer);
+        }
+    }
+
+    fn should_quit(&self) -> bool {
+        self.scenes.is_empty()
+    }
+}
+
+#[cfg(all(test, feature = "headless-backend"))]
+mod tests {
+    use core::application::Application;
+    use core::drawing::cell::Cell;
+    use core::drawing::cell_buffer::CellBuffer;
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::size_2d::Size2d;
+    use core::events::event::Event;
+    use core::game_loop::game::Game;
+    use core::scenes::scene::Scene;
+    use core::scenes::scene_stack::SceneStack;
+    use core::scenes::transition::Transition;
+    use headless::application::HeadlessApplication;
+    use std::time::Duration;
+
+    /// A scene that draws its name and requests a transition on its first
+    /// update.
+    struct TestScene {
+        name: char,
+        modal: bool,
+        transition: Option<Transition>,
+    }
+
+    impl TestScene {
+        fn create(name: char, modal: bool, transition: Transition) -> Box<TestScene> {
+            Box::new(TestScene {
+                name,
+                modal,
+                transition: Some(transition),
+            })
+        }
+    }
+
+    impl Scene for TestScene {
+        fn handle_event(&mut self, _event: &Event, _application: &dyn Application) -> Transition {
+            Transition::None
+        }
+
+        fn update(&mut self, _dt: Duration, _application: &dyn Application) -> Transition {
+            self.transition.take().unwrap_or(Transition::None)
+        }
+
+        fn draw(&mut self, buffer: &mut CellBuffer) {
+            let x = (0..buffer.size.width as i32)
+                .find(|x| buffer.get(Point2d::new(*x, 0)).character == '.')
+                .unwrap_or(0);
+            buffer.set(Point2d::new(x, 0), Cell::new_default(self.name));
+        }
+
+        fn is_transparent(&self) -> bool {
+            true
+        }
+
+        fn is_modal(&self) -> bool {
+            self.modal
+        }
+    }
+
+    fn get_names(stack: &mut SceneStack) -> String {
+        let mut buffer = CellBuffer::new(Cell::new_default('.'), Size2d::new(4, 1));
+        stack.draw(&mut buffer);
+        buffer
+            .iter()
+            .map(|cell| cell.character)
+            .filter(|character| *character != '.')
+            .collect()
+    }
+
+    #[test]
+    fn lower_scene_pops_itself() {
+        let mut stack = SceneStack::new();
+        stack.push(TestScene::create('a', true, Transition::None));
+        stack.push(TestScene::create('b', true, Transition::Pop));
+        stack.push(TestScene::create('c', false, Transition::None));
+
+        let application = HeadlessApplication::new(Size2d::new(4, 1));
+        stack.update(Duration::from_millis(16), &application);
+        assert_eq!(get_names(&mut stack), "ac");
+    }
+
+    #[test]
+    fn lower_scene_replaces_itself() {
+        let mut stack = SceneStack::new();
+        let replacement = TestScene::create('d', true, Transition::None);
+        stack.push(TestScene::create(
+            'b',
+            true,
+            Transition::Replace(replacement),
+        ));
+        stack.push(TestScene::create('c', false, Transition::None));
+
+        let application = HeadlessApplication::new(Size2d::new(4, 1));
+        stack.update(Duration::from_millis(16), &application);
+        assert_eq!(get_names(&mut stack), "dc");
+    }
+
+    #[test]
+    fn lower_scene_pushes_above_itself() {
+        let mut stack = SceneStack::new();
+        let pushed = TestScene::create('d', true, Transition::None);
+        stack.push(TestScene::create('b', true, Transition::Push(pushed)));
+        stack.push(TestScene::create('c', false, Transition::None));
+
+        let application = HeadlessApplication::new(Size2d::new(4, 1));
+        stack.update(Duration::from_millis(16), &application);
+        assert_eq!(get_names(&mut stack), "bdc");
+    }
+}
@@ -0,0 +1,21 @@
+use core::scenes::scene::Scene;
+
+/// The change a scene requests to the scene stack. The transition applies
+/// to the scene that requested it, which isn't the top one when it is below
+/// a non modal scene.
+pub enum Transition {
+    /// Keeps the stack as it is.
+    None,
+
+    /// Puts a new scene right above the requesting one.
+    Push(Box<dyn Scene>),
+
+    /// Removes the requesting scene.
+    Pop,
+
+    /// Removes the requesting scene and puts a new one in its place.
+    Replace(Box<dyn Scene>),
+
+    /// Removes every scene, which stops the game.
+    Quit,
+}
@@ -1,5 +1,7 @@
 use core::drawing::cell_buffer::CellBuffer;
+use core::drawing::color_depth::ColorDepth;
 use core::drawing::point_2d::Point2d;
+use core::drawing::row_span::RowSpan;
 use core::drawing::size_2d::Size2d;
 use core::window::Window;
 use core::Result;
@@ -17,12 +19,31 @@ pub trait Terminal {
     /// Gets the current console size in character units.
     fn get_console_size(&self) -> Result<Size2d>;
 
+    /// Gets the amount of colors the terminal can display. Colors outside of
+    /// the available palette are replaced by the nearest available one.
+    fn get_color_depth(&self) -> Result<ColorDepth>;
+
     /// Gets the character size in pixel units.
-    fn get_char_size(&self, window: &Window) -> Result<Size2d>;
+    fn get_char_size(&self, window: &dyn Window) -> Result<Size2d>;
 
     /// Clears the console screen.
     fn clear(&self) -> Result<()>;
 
     /// Draws a `CellBuffer` to the screen.
     fn write(&self, cell_buffer: &CellBuffer) -> Result<()>;
+
+    /// Draws a `CellBuffer` to the screen, knowing that only the cells in the
+    /// given regions changed since the previous write, like the regions of
+    /// `CellBuffer::take_dirty_regions`. Terminals that can't use them write
+    /// the buffer like `write`.
+    ///
+    /// The regions are trusted without comparing the frames, so they must
+    /// cover every change since the previous successful write, which must
+    /// have been of the same buffer. After a failed write, or when drawing
+    /// another buffer, use `write` or `CellBuffer::mark_all_dirty`. A write
+    /// after `clear` or a resize always draws the whole buffer.
+    fn write_regions(&self, cell_buffer: &CellBuffer, regions: &[RowSpan]) -> Result<()> {
+        let _ = regions;
+        self.write(cell_buffer)
+    }
 }
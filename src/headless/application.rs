@@ -0,0 +1,313 @@
+use core::application::Application;
+use core::drawing::size_2d::Size2d;
+use core::events::event::{Event, WindowEvent, WindowEventType};
+use core::events::event_queue::EventQueue;
+use core::input::keyboard_state::KeyboardState;
+use core::input::mouse_state::MouseState;
+use core::mouse::Mouse;
+use core::terminal::Terminal;
+use core::window::Window;
+use core::Result;
+use headless::mouse::HeadlessMouse;
+use headless::terminal::HeadlessTerminal;
+use headless::waker::HeadlessWaker;
+use headless::window::HeadlessWindow;
+use std::collections::vec_deque::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Application that runs without a console. The native events are replaced
+/// by a scripted queue of events, and the window reports one pixel per cell.
+pub struct HeadlessApplication {
+    window: HeadlessWindow,
+    terminal: HeadlessTerminal,
+    mouse: HeadlessMouse,
+    event_queue: EventQueue,
+    mouse_state: MouseState,
+    keyboard_state: KeyboardState,
+    scripted_events: VecDeque<Event>,
+    waker: Arc<HeadlessWaker>,
+}
+
+impl HeadlessApplication {
+    pub fn new(console_size: Size2d) -> HeadlessApplication {
+        let waker = Arc::new(HeadlessWaker::new());
+        let mut event_queue = EventQueue::new();
+        event_queue.set_waker(waker.clone());
+
+        HeadlessApplication {
+            window: HeadlessWindow::new(console_size),
+            terminal: HeadlessTerminal::new(console_size),
+            mouse: HeadlessMouse::new(),
+            event_queue,
+            mouse_state: MouseState::new(),
+            keyboard_state: KeyboardState::new(),
+            scripted_events: VecDeque::new(),
+            waker,
+        }
+    }
+
+    /// Schedules an event to be reported by the next `listen_events` call.
+    pub fn push_event(&mut self, event: Event) {
+        self.scripted_events.push_back(event);
+    }
+
+    /// Schedules a sequence of events to be reported by the next
+    /// `listen_events` call.
+    pub fn push_events<I: IntoIterator<Item = Event>>(&mut self, events: I) {
+        self.scripted_events.extend(events);
+    }
+
+    /// Resizes the console, and the window with it, and schedules the
+    /// resize event a real console would report.
+    pub fn resize(&mut self, console_size: Size2d) -> Result<()> {
+        self.terminal.set_console_size(console_size);
+        self.window.set_window_size(console_size)?;
+        self.scripted_events.push_back(Event::Window(WindowEvent {
+            event_type: WindowEventType::WindowResize,
+            position: self.window.get_window_position()?,
+            size: console_size,
+        }));
+
+        Ok(())
+    }
+
+    /// Gets the concrete terminal, to inspect the presented frames.
+    #[inline]
+    pub fn get_headless_terminal(&self) -> &HeadlessTerminal {
+        &self.terminal
+    }
+
+    /// Gets the concrete mouse, to inspect the cursor visibility.
+    #[inline]
+    pub fn get_headless_mouse(&self) -> &HeadlessMouse {
+        &self.mouse
+    }
+}
+
+impl Application for HeadlessApplication {
+    #[inline]
+    fn get_terminal(&self) -> &dyn Terminal {
+        &self.terminal
+    }
+
+    #[inline]
+    fn get_window(&self) -> &dyn Window {
+        &self.window
+    }
+
+    #[inline]
+    fn get_mouse(&self) -> &dyn Mouse {
+        &self.mouse
+    }
+
+    #[inline]
+    fn get_mouse_state(&self) -> &MouseState {
+        &self.mouse_state
+    }
+
+    #[inline]
+    fn get_keyboard_state(&self) -> &KeyboardState {
+        &self.keyboard_state
+    }
+
+    #[inline]
+    fn get_event_queue(&self) -> &EventQueue {
+        &self.event_queue
+    }
+
+    #[inline]
+    fn get_mut_event_queue(&mut self) -> &mut EventQueue {
+        &mut self.event_queue
+    }
+
+    fn listen_events(&mut self) -> Result<()> {
+        self.keyboard_state.begin_frame();
+        self.mouse_state.begin_frame();
+        self.event_queue.receive_sent_events();
+
+        while let Some(mut event) = self.scripted_events.pop_front() {
+            match event {
+                Event::Mouse(ref mut mouse) => {
+                    self.mouse.set_position(mouse.position)?;
+                    self.mouse_state.update_from_event(mouse)
+                }
+                Event::Keyboard(keyboard) => self.keyboard_state.update_from_event(keyboard),
+                _ => (),
+            }
+
+            self.event_queue.add_event(event);
+        }
+
+        Ok(())
+    }
+
+    /// Reports the scripted events right away. Without them, it sleeps until
+    /// another thread sends an event or the timeout expires. Without a
+    /// timeout only a sent event ends the wait, so a `GameRunner` in turn
+    /// based mode needs a script that closes the window, or a sender.
+    fn wait_events(&mut self, timeout: Option<Duration>) -> Result<()> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            self.listen_events()?;
+
+            if !self.event_queue.is_empty() {
+                return Ok(());
+            }
+
+            match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+
+                    if now >= deadline {
+                        return Ok(());
+                    }
+
+                    self.waker.wait(Some(deadline - now));
+                }
+                None => self.waker.wait(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::application::Application;
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::size_2d::Size2d;
+    use core::events::event::{Event, MouseEvent, MouseEventType, WindowEventType};
+    use core::input::mouse_button::MouseButton;
+    use headless::application::HeadlessApplication;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    fn click(position: Point2d) -> Event {
+        Event::Mouse(MouseEvent {
+            event_type: MouseEventType::Click,
+            left_button: true,
+            middle_button: false,
+            right_button: false,
+            extra_button_1: false,
+            extra_button_2: false,
+            extra_button_3: false,
+            extra_button_4: false,
+            position,
+        })
+    }
+
+    #[test]
+    fn listen_events_reports_the_script_in_order() {
+        let mut application = HeadlessApplication::new(Size2d::new(10, 5));
+        application.push_events(vec![
+            Event::Text("a".to_string()),
+            click(Point2d::new(3, 2)),
+        ]);
+
+        application.listen_events().unwrap();
+
+        let queue = application.get_mut_event_queue();
+        assert_eq!(queue.len(), 2);
+
+        match queue.get_event() {
+            Some(Event::Text(text)) => assert_eq!(text, "a"),
+            event => panic!("unexpected event {:?}", event),
+        }
+
+        match queue.get_event() {
+            Some(Event::Mouse(mouse)) => assert_eq!(mouse.position, Point2d::new(3, 2)),
+            event => panic!("unexpected event {:?}", event),
+        }
+
+        assert!(application.get_mouse_state().is_pressed(MouseButton::Left));
+        assert_eq!(
+            application.get_mouse().get_client_position().unwrap(),
+            Point2d::new(3, 2)
+        );
+
+        // the script is consumed, the next frame has no events.
+        application.listen_events().unwrap();
+        assert!(application.get_event_queue().is_empty());
+        assert!(!application.get_mouse_state().is_pressed(MouseButton::Left));
+    }
+
+    #[test]
+    fn resize_reports_the_new_console_size() {
+        let mut application = HeadlessApplication::new(Size2d::new(10, 5));
+        application.resize(Size2d::new(20, 8)).unwrap();
+        application.listen_events().unwrap();
+
+        assert_eq!(
+            application.get_terminal().get_console_size().unwrap(),
+            Size2d::new(20, 8)
+        );
+
+        match application.get_mut_event_queue().get_event() {
+            Some(Event::Window(window)) => {
+                assert_eq!(window.event_type, WindowEventType::WindowResize);
+                assert_eq!(window.size, Size2d::new(20, 8));
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    #[test]
+    fn wait_events_sleeps_for_the_timeout() {
+        let mut application = HeadlessApplication::new(Size2d::new(10, 5));
+        let start = Instant::now();
+
+        application
+            .wait_events(Some(Duration::from_millis(50)))
+            .unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert!(application.get_event_queue().is_empty());
+    }
+
+    #[test]
+    fn wait_events_wakes_up_on_a_sent_event() {
+        let mut application = HeadlessApplication::new(Size2d::new(10, 5));
+        let sender = application.get_event_sender();
+        let start = Instant::now();
+
+        let thread = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            sender.send_user(7u32).unwrap();
+        });
+
+        application
+            .wait_events(Some(Duration::from_secs(10)))
+            .unwrap();
+        thread.join().unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        match application.get_mut_event_queue().get_event() {
+            Some(Event::User(value)) => assert_eq!(value.downcast_ref::<u32>(), Some(&7)),
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    #[test]
+    fn wait_events_without_timeout_waits_for_a_sent_event() {
+        let mut application = HeadlessApplication::new(Size2d::new(10, 5));
+        let sender = application.get_event_sender();
+        let start = Instant::now();
+
+        let thread = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            sender.send(Event::Text("a".to_string())).unwrap();
+        });
+
+        application.wait_events(None).unwrap();
+        thread.join().unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        match application.get_mut_event_queue().get_event() {
+            Some(Event::Text(text)) => assert_eq!(text, "a"),
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod application;
+pub mod mouse;
+pub mod terminal;
+pub mod waker;
+pub mod window;
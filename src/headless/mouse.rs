@@ -0,0 +1,51 @@
+use core::drawing::point_2d::Point2d;
+use core::mouse::Mouse;
+use core::Result;
+use std::cell::Cell;
+
+/// In-memory mouse that stores the position and cursor visibility it gets.
+#[derive(Debug)]
+pub struct HeadlessMouse {
+    position: Cell<Point2d>,
+    visible: Cell<bool>,
+}
+
+impl HeadlessMouse {
+    pub fn new() -> HeadlessMouse {
+        HeadlessMouse {
+            position: Cell::new(Point2d::empty()),
+            visible: Cell::new(true),
+        }
+    }
+
+    /// Gets whether the mouse cursor is visible.
+    pub fn is_cursor_visible(&self) -> bool {
+        self.visible.get()
+    }
+}
+
+impl Default for HeadlessMouse {
+    fn default() -> HeadlessMouse {
+        HeadlessMouse::new()
+    }
+}
+
+impl Mouse for HeadlessMouse {
+    fn get_absolute_position(&self) -> Result<Point2d> {
+        Ok(self.position.get())
+    }
+
+    fn get_client_position(&self) -> Result<Point2d> {
+        Ok(self.position.get())
+    }
+
+    fn set_position(&self, position: Point2d) -> Result<()> {
+        self.position.set(position);
+        Ok(())
+    }
+
+    fn show_cursor(&self, visible: bool) -> Result<()> {
+        self.visible.set(visible);
+        Ok(())
+    }
+}
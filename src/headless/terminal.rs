@@ -0,0 +1,195 @@
+use core::drawing::cell::Cell;
+use core::drawing::cell_buffer::CellBuffer;
+use core::drawing::color_depth::ColorDepth;
+use core::drawing::point_2d::Point2d;
+use core::drawing::size_2d::Size2d;
+use core::terminal::Terminal;
+use core::window::Window;
+use core::Result;
+use std::cell::{Cell as InteriorCell, Ref, RefCell};
+
+/// In-memory terminal that keeps the last `CellBuffer` written to it, so the
+/// presented frames can be inspected without a real console.
+#[derive(Debug)]
+pub struct HeadlessTerminal {
+    console_size: InteriorCell<Size2d>,
+    cursor: InteriorCell<Point2d>,
+    cursor_visible: InteriorCell<bool>,
+    color_depth: InteriorCell<ColorDepth>,
+    last_buffer: RefCell<Option<CellBuffer>>,
+    disposed: InteriorCell<bool>,
+}
+
+impl HeadlessTerminal {
+    pub fn new(console_size: Size2d) -> HeadlessTerminal {
+        HeadlessTerminal {
+            console_size: InteriorCell::new(console_size),
+            cursor: InteriorCell::new(Point2d::empty()),
+            cursor_visible: InteriorCell::new(true),
+            color_depth: InteriorCell::new(ColorDepth::TrueColor),
+            last_buffer: RefCell::new(None),
+            disposed: InteriorCell::new(false),
+        }
+    }
+
+    /// Changes the console size, simulating the user resizing the console.
+    pub fn set_console_size(&self, console_size: Size2d) {
+        self.console_size.set(console_size);
+    }
+
+    /// Changes the color depth, simulating a terminal with a limited palette.
+    /// The written buffers are stored with their colors quantized.
+    pub fn set_color_depth(&self, color_depth: ColorDepth) {
+        self.color_depth.set(color_depth);
+    }
+
+    /// Gets the last buffer written or cleared, if any.
+    pub fn get_last_buffer(&self) -> Ref<'_, Option<CellBuffer>> {
+        self.last_buffer.borrow()
+    }
+
+    /// Gets the console cursor position.
+    pub fn get_cursor(&self) -> Point2d {
+        self.cursor.get()
+    }
+
+    /// Gets whether the console cursor is visible.
+    pub fn is_cursor_visible(&self) -> bool {
+        self.cursor_visible.get()
+    }
+
+    /// Gets whether the terminal has been disposed.
+    pub fn is_disposed(&self) -> bool {
+        self.disposed.get()
+    }
+}
+
+impl Terminal for HeadlessTerminal {
+    fn dispose(&self) -> Result<()> {
+        self.disposed.set(true);
+        Ok(())
+    }
+
+    fn set_cursor_visibility(&self, visible: bool) -> Result<()> {
+        self.cursor_visible.set(visible);
+        Ok(())
+    }
+
+    fn set_cursor(&self, position: Point2d) -> Result<()> {
+        self.cursor.set(position);
+        Ok(())
+    }
+
+    fn get_console_size(&self) -> Result<Size2d> {
+        Ok(self.console_size.get())
+    }
+
+    fn get_color_depth(&self) -> Result<ColorDepth> {
+        Ok(self.color_depth.get())
+    }
+
+    fn get_char_size(&self, window: &dyn Window) -> Result<Size2d> {
+        let console_size = self.get_console_size()?;
+        let client_size = window.get_window_client_size()?;
+
+        if console_size.width == 0 || console_size.height == 0 {
+            return Ok(Size2d::empty());
+        }
+
+        Ok(Size2d::new(
+            client_size.width / console_size.width,
+            client_size.height / console_size.height,
+        ))
+    }
+
+    fn clear(&self) -> Result<()> {
+        *self.last_buffer.borrow_mut() = Some(CellBuffer::new(
+            Cell::new_default(' '),
+            self.console_size.get(),
+        ));
+        Ok(())
+    }
+
+    fn write(&self, cell_buffer: &CellBuffer) -> Result<()> {
+        let color_depth = self.color_depth.get();
+        let mut last_buffer = cell_buffer.clone();
+
+        for cell in last_buffer.iter_mut() {
+            cell.foreground = cell.foreground.quantize(color_depth);
+            cell.background = cell.background.quantize(color_depth);
+        }
+
+        *self.last_buffer.borrow_mut() = Some(last_buffer);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::cell::Cell;
+    use core::drawing::cell_buffer::CellBuffer;
+    use core::drawing::color::Color;
+    use core::drawing::color_depth::ColorDepth;
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::size_2d::Size2d;
+    use core::terminal::Terminal;
+    use headless::terminal::HeadlessTerminal;
+
+    #[test]
+    fn write_keeps_the_last_buffer() {
+        let terminal = HeadlessTerminal::new(Size2d::new(3, 2));
+        assert!(terminal.get_last_buffer().is_none());
+
+        let mut buffer = CellBuffer::new(Cell::new_default('.'), Size2d::new(3, 2));
+        buffer.set(Point2d::new(2, 1), Cell::new_default('x'));
+        terminal.write(&buffer).unwrap();
+
+        let last_buffer = terminal.get_last_buffer();
+        let last_buffer = last_buffer.as_ref().unwrap();
+        assert_eq!(last_buffer.get(Point2d::new(2, 1)).character, 'x');
+        assert_eq!(last_buffer.get(Point2d::new(0, 0)).character, '.');
+    }
+
+    #[test]
+    fn write_quantizes_to_the_color_depth() {
+        let terminal = HeadlessTerminal::new(Size2d::new(1, 1));
+        terminal.set_color_depth(ColorDepth::Ansi16);
+
+        let cell = Cell::new('x', Color::Rgb(250, 10, 10), Color::Rgb(0, 0, 120));
+        terminal
+            .write(&CellBuffer::new(cell, Size2d::new(1, 1)))
+            .unwrap();
+
+        let last_buffer = terminal.get_last_buffer();
+        let cell = last_buffer.as_ref().unwrap().get(Point2d::empty());
+        assert_eq!(cell.foreground, Color::Red);
+        assert_eq!(cell.background, Color::DarkBlue);
+    }
+
+    #[test]
+    fn clear_blanks_the_console() {
+        let terminal = HeadlessTerminal::new(Size2d::new(2, 1));
+        let buffer = CellBuffer::new(Cell::new_default('x'), Size2d::new(2, 1));
+        terminal.write(&buffer).unwrap();
+
+        terminal.set_console_size(Size2d::new(4, 2));
+        terminal.clear().unwrap();
+
+        let last_buffer = terminal.get_last_buffer();
+        let last_buffer = last_buffer.as_ref().unwrap();
+        assert_eq!(last_buffer.size, Size2d::new(4, 2));
+        assert!(last_buffer.iter().all(|cell| cell.character == ' '));
+    }
+
+    #[test]
+    fn cursor_and_dispose_are_recorded() {
+        let terminal = HeadlessTerminal::new(Size2d::new(2, 1));
+        terminal.set_cursor(Point2d::new(1, 0)).unwrap();
+        terminal.set_cursor_visibility(false).unwrap();
+        terminal.dispose().unwrap();
+
+        assert_eq!(terminal.get_cursor(), Point2d::new(1, 0));
+        assert!(!terminal.is_cursor_visible());
+        assert!(terminal.is_disposed());
+    }
+}
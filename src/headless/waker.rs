@@ -0,0 +1,64 @@
+use core::events::waker::Waker;
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+/// Wakes up a headless application sleeping in `wait_events` when another
+/// thread sends an event.
+#[derive(Debug)]
+pub struct HeadlessWaker {
+    woken: Mutex<bool>,
+    condition: Condvar,
+}
+
+impl HeadlessWaker {
+    pub fn new() -> HeadlessWaker {
+        HeadlessWaker {
+            woken: Mutex::new(false),
+            condition: Condvar::new(),
+        }
+    }
+
+    /// Sleeps until `wake` is called or the timeout expires. Without a
+    /// timeout it sleeps until `wake` is called. A wake that happened since
+    /// the previous wait returns right away.
+    pub fn wait(&self, timeout: Option<Duration>) {
+        let woken = match self.woken.lock() {
+            Ok(woken) => woken,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        let mut woken = match timeout {
+            Some(timeout) => match self
+                .condition
+                .wait_timeout_while(woken, timeout, |woken| !*woken)
+            {
+                Ok((woken, _)) => woken,
+                Err(poisoned) => poisoned.into_inner().0,
+            },
+            None => match self.condition.wait_while(woken, |woken| !*woken) {
+                Ok(woken) => woken,
+                Err(poisoned) => poisoned.into_inner(),
+            },
+        };
+
+        *woken = false;
+    }
+}
+
+impl Default for HeadlessWaker {
+    fn default() -> HeadlessWaker {
+        HeadlessWaker::new()
+    }
+}
+
+impl Waker for HeadlessWaker {
+    fn wake(&self) {
+        let mut woken = match self.woken.lock() {
+            Ok(woken) => woken,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        *woken = true;
+        self.condition.notify_all();
+    }
+}
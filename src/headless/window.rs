@@ -0,0 +1,45 @@
+use core::drawing::point_2d::Point2d;
+use core::drawing::size_2d::Size2d;
+use core::window::Window;
+use core::Result;
+use std::cell::Cell;
+
+/// In-memory window that just stores the position and size it gets.
+#[derive(Debug)]
+pub struct HeadlessWindow {
+    position: Cell<Point2d>,
+    size: Cell<Size2d>,
+}
+
+impl HeadlessWindow {
+    pub fn new(size: Size2d) -> HeadlessWindow {
+        HeadlessWindow {
+            position: Cell::new(Point2d::empty()),
+            size: Cell::new(size),
+        }
+    }
+}
+
+impl Window for HeadlessWindow {
+    fn get_window_client_size(&self) -> Result<Size2d> {
+        Ok(self.size.get())
+    }
+
+    fn get_window_size(&self) -> Result<Size2d> {
+        Ok(self.size.get())
+    }
+
+    fn set_window_size(&self, size: Size2d) -> Result<()> {
+        self.size.set(size);
+        Ok(())
+    }
+
+    fn get_window_position(&self) -> Result<Point2d> {
+        Ok(self.position.get())
+    }
+
+    fn set_window_position(&self, position: Point2d) -> Result<()> {
+        self.position.set(position);
+        Ok(())
+    }
+}
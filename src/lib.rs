@@ -0,0 +1,7 @@
+pub mod core;
+#[cfg(feature = "headless-backend")]
+pub mod headless;
+#[cfg(all(unix, feature = "unix-backend"))]
+pub mod unix;
+#[cfg(all(windows, feature = "windows-backend"))]
+pub mod windows;
@@ -0,0 +1,325 @@
+use core::application::Application;
+use core::drawing::point_2d::Point2d;
+use core::drawing::size_2d::Size2d;
+use core::events::event::{Event, WindowEvent, WindowEventType};
+use core::events::event_queue::EventQueue;
+use core::input::keyboard_state::KeyboardState;
+use core::input::mouse_state::MouseState;
+use core::mouse::Mouse;
+use core::terminal::Terminal;
+use core::window::Window;
+use core::Result;
+use std::io;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use unix::input::InputParser;
+use unix::libc::{c_int, nfds_t, poll, pollfd, read, EAGAIN, EINTR, POLLIN};
+use unix::mouse::UnixMouse;
+use unix::signal::{clear_wake, get_wake_fd, install_handlers, take_close_requested, take_resized};
+use unix::terminal::UnixTerminal;
+use unix::waker::UnixWaker;
+use unix::window::UnixWindow;
+use unix::write_sequence;
+
+/// Enables the mouse button, drag and motion tracking with SGR reports, the
+/// focus reports and the bracketed paste.
+const ENABLE_REPORTING_SEQUENCE: &str =
+    "\x1b[?1000h\x1b[?1002h\x1b[?1003h\x1b[?1006h\x1b[?1004h\x1b[?2004h";
+
+/// Asks the terminal whether it supports the SGR pixel mouse mode (DECRQM).
+const QUERY_PIXEL_MOUSE_SEQUENCE: &str = "\x1b[?1016$p";
+
+/// Enables the SGR pixel mouse mode, which replaces the cells of the SGR
+/// reports with pixels.
+const ENABLE_PIXEL_MOUSE_SEQUENCE: &str = "\x1b[?1016h";
+
+/// How long the rest of an escape sequence is awaited before its start is
+/// reported as the escape key, or as alt with `[` or `O`.
+const ESCAPE_TIMEOUT: Duration = Duration::from_millis(50);
+
+pub struct UnixApplication {
+    window: UnixWindow,
+    terminal: UnixTerminal,
+    mouse: UnixMouse,
+    event_queue: EventQueue,
+    mouse_state: MouseState,
+    keyboard_state: KeyboardState,
+    input_parser: InputParser,
+    escape_deadline: Option<Instant>,
+    pixel_mouse: bool,
+}
+
+impl UnixApplication {
+    pub fn create() -> Result<UnixApplication> {
+        let mut application = UnixApplication {
+            window: UnixWindow::new(),
+            terminal: UnixTerminal::create()?,
+            mouse: UnixMouse::new(),
+            event_queue: EventQueue::new(),
+            mouse_state: MouseState::new(),
+            keyboard_state: KeyboardState::new(),
+            input_parser: InputParser::new(),
+            escape_deadline: None,
+            pixel_mouse: false,
+        };
+
+        install_handlers()?;
+        application.event_queue.set_waker(Arc::new(UnixWaker));
+        write_sequence(ENABLE_REPORTING_SEQUENCE)?;
+        write_sequence(QUERY_PIXEL_MOUSE_SEQUENCE)?;
+
+        Ok(application)
+    }
+
+    /// Waits `ESCAPE_TIMEOUT` after the last read for the rest of an
+    /// incomplete sequence, then parses what arrived.
+    fn check_incomplete_sequence(&mut self, received: bool, events: &mut Vec<Event>) {
+        if !self.input_parser.has_incomplete_sequence() {
+            self.escape_deadline = None;
+            return;
+        }
+
+        let now = Instant::now();
+
+        match self.escape_deadline {
+            Some(deadline) if !received && now >= deadline => {
+                self.input_parser.flush(events);
+                self.escape_deadline = None;
+            }
+            Some(_) if !received => (),
+            _ => self.escape_deadline = Some(now + ESCAPE_TIMEOUT),
+        }
+    }
+
+    /// Creates a window event. Terminals don't report the window position.
+    fn get_window_event(&self, event_type: WindowEventType) -> Result<Event> {
+        Ok(Event::Window(WindowEvent {
+            event_type,
+            position: Point2d::empty(),
+            size: self.terminal.get_console_size()?,
+        }))
+    }
+
+    /// Gets the character size in pixels, or none when the terminal doesn't
+    /// report the pixel size.
+    fn get_pixel_char_size(&self) -> Result<Option<Size2d>> {
+        let char_size = self.terminal.get_char_size(&self.window)?;
+
+        if char_size.width == 0 || char_size.height == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(char_size))
+    }
+
+    /// Switches to the SGR pixel mouse mode once the terminal answered that
+    /// it supports it, as long as the pixel size of the cells is known.
+    fn check_pixel_mouse(&mut self) -> Result<()> {
+        if self.input_parser.take_pixel_mouse_supported() != Some(true) {
+            return Ok(());
+        }
+
+        if let Some(char_size) = self.get_pixel_char_size()? {
+            write_sequence(ENABLE_PIXEL_MOUSE_SEQUENCE)?;
+            self.input_parser.set_pixel_mouse(Some(char_size));
+            self.pixel_mouse = true;
+        }
+
+        Ok(())
+    }
+}
+
+impl Application for UnixApplication {
+    #[inline]
+    fn get_terminal(&self) -> &dyn Terminal {
+        &self.terminal
+    }
+
+    #[inline]
+    fn get_window(&self) -> &dyn Window {
+        &self.window
+    }
+
+    #[inline]
+    fn get_mouse(&self) -> &dyn Mouse {
+        &self.mouse
+    }
+
+    #[inline]
+    fn get_mouse_state(&self) -> &MouseState {
+        &self.mouse_state
+    }
+
+    #[inline]
+    fn get_keyboard_state(&self) -> &KeyboardState {
+        &self.keyboard_state
+    }
+
+    #[inline]
+    fn get_event_queue(&self) -> &EventQueue {
+        &self.event_queue
+    }
+
+    #[inline]
+    fn get_mut_event_queue(&mut self) -> &mut EventQueue {
+        &mut self.event_queue
+    }
+
+    fn listen_events(&mut self) -> Result<()> {
+        self.keyboard_state.begin_frame();
+        self.mouse_state.begin_frame();
+
+        let mut bytes = [0u8; 1024];
+        let mut events = Vec::new();
+
+        // the signal handlers only record the signals, the events are
+        // created here.
+        clear_wake();
+        self.event_queue.receive_sent_events();
+
+        if take_resized() {
+            events.push(self.get_window_event(WindowEventType::WindowResize)?);
+
+            // the pixel reports are converted with the new cell size.
+            if self.pixel_mouse {
+                let char_size = self.get_pixel_char_size()?;
+                self.input_parser.set_pixel_mouse(char_size);
+            }
+        }
+
+        if take_close_requested() {
+            events.push(self.get_window_event(WindowEventType::WindowClose)?);
+        }
+
+        let mut received = false;
+
+        loop {
+            let bytes_read = unsafe {
+                read(
+                    self.terminal.input_fd,
+                    bytes.as_mut_ptr() as *mut _,
+                    bytes.len(),
+                )
+            };
+
+            if bytes_read < 0 {
+                let error = io::Error::last_os_error();
+
+                match error.raw_os_error() {
+                    Some(EAGAIN) | Some(EINTR) => break,
+                    _ => return Err(error.into()),
+                }
+            }
+
+            if bytes_read == 0 {
+                break;
+            }
+
+            self.input_parser
+                .parse(&bytes[..bytes_read as usize], &mut events);
+            received = true;
+
+            if (bytes_read as usize) < bytes.len() {
+                break;
+            }
+        }
+
+        self.check_incomplete_sequence(received, &mut events);
+        self.check_pixel_mouse()?;
+
+        if let Some(position) = self.input_parser.get_pixel_position() {
+            self.mouse.update_pixel_position(position);
+        }
+
+        for event in events {
+            let mut event = match event {
+                Event::Window(window) => self.get_window_event(window.event_type)?,
+                event => event,
+            };
+
+            match event {
+                Event::Mouse(ref mut mouse) => {
+                    self.mouse.update_position(mouse.position);
+                    self.mouse_state.update_from_event(mouse)
+                }
+                Event::Keyboard(keyboard) => self.keyboard_state.update_from_event(keyboard),
+                _ => (),
+            }
+
+            self.event_queue.add_event(event);
+        }
+
+        Ok(())
+    }
+
+    fn wait_events(&mut self, timeout: Option<Duration>) -> Result<()> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            self.listen_events()?;
+
+            if !self.event_queue.is_empty() {
+                return Ok(());
+            }
+
+            let now = Instant::now();
+
+            if deadline.is_some_and(|deadline| now >= deadline) {
+                return Ok(());
+            }
+
+            // an incomplete sequence is parsed when its time runs out.
+            let milliseconds = match (deadline, self.escape_deadline) {
+                (Some(deadline), Some(escape_deadline)) => {
+                    get_milliseconds(deadline.min(escape_deadline).saturating_duration_since(now))
+                }
+                (Some(deadline), None) | (None, Some(deadline)) => {
+                    get_milliseconds(deadline.saturating_duration_since(now))
+                }
+                (None, None) => -1,
+            };
+
+            wait_for_input(&[self.terminal.input_fd, get_wake_fd()], milliseconds)?;
+        }
+    }
+}
+
+/// Sleeps until one of the file descriptors is readable, a signal arrives or
+/// the timeout expires. A negative timeout waits indefinitely.
+fn wait_for_input(fds: &[c_int], milliseconds: c_int) -> Result<()> {
+    let mut poll_fds: Vec<pollfd> = fds
+        .iter()
+        .filter(|fd| **fd != -1)
+        .map(|fd| pollfd {
+            fd: *fd,
+            events: POLLIN,
+            revents: 0,
+        })
+        .collect();
+
+    let success = unsafe {
+        poll(
+            poll_fds.as_mut_ptr(),
+            poll_fds.len() as nfds_t,
+            milliseconds,
+        )
+    };
+
+    if success == -1 {
+        let error = io::Error::last_os_error();
+
+        if error.raw_os_error() != Some(EINTR) {
+            return Err(error.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Gets the milliseconds of a duration, rounded up so short timeouts don't
+/// become busy waits.
+#[inline]
+fn get_milliseconds(duration: Duration) -> c_int {
+    let milliseconds = duration.as_nanos().div_ceil(1_000_000);
+    milliseconds.min(c_int::MAX as u128) as c_int
+}
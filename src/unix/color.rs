@@ -0,0 +1,70 @@
+use core::drawing::color::Color;
+use core::drawing::color_depth::ColorDepth;
+use core::drawing::style::Style;
+use std::env::var;
+
+/// Detects the color depth of the terminal from the environment variables.
+pub fn get_color_depth_from_environment() -> ColorDepth {
+    let color_term = var("COLORTERM").unwrap_or_default();
+    let term = var("TERM").unwrap_or_default();
+
+    if color_term == "truecolor" || color_term == "24bit" || term.ends_with("-direct") {
+        ColorDepth::TrueColor
+    } else if term.contains("256color") {
+        ColorDepth::Indexed256
+    } else {
+        ColorDepth::Ansi16
+    }
+}
+
+/// Gets the SGR parameters that set the given foreground color. The color
+/// must be already quantized to the terminal color depth.
+#[inline]
+pub fn get_foreground_parameters(color: Color) -> String {
+    get_parameters(color, 30, 90, 38)
+}
+
+/// Gets the SGR parameters that set the given background color. The color
+/// must be already quantized to the terminal color depth.
+#[inline]
+pub fn get_background_parameters(color: Color) -> String {
+    get_parameters(color, 40, 100, 48)
+}
+
+/// Gets the SGR parameters that set the attributes of the given style, each
+/// one followed by a semicolon.
+#[inline]
+pub fn get_style_parameters(style: Style) -> String {
+    let mut parameters = String::new();
+
+    for &(attribute, code) in [
+        (Style::BOLD, "1;"),
+        (Style::DIM, "2;"),
+        (Style::ITALIC, "3;"),
+        (Style::UNDERLINE, "4;"),
+        (Style::BLINK, "5;"),
+        (Style::REVERSE, "7;"),
+        (Style::STRIKETHROUGH, "9;"),
+    ]
+    .iter()
+    {
+        if style.contains(attribute) {
+            parameters.push_str(code);
+        }
+    }
+
+    parameters
+}
+
+#[inline]
+fn get_parameters(color: Color, base: u8, bright_base: u8, extended: u8) -> String {
+    match (color.get_ansi_index(), color) {
+        (Some(index), _) if index < 8 => format!("{}", base + index),
+        (Some(index), _) => format!("{}", bright_base + index - 8),
+        (None, Color::Indexed(index)) => format!("{};5;{}", extended, index),
+        (None, _) => {
+            let (red, green, blue) = color.to_rgb();
+            format!("{};2;{};{};{}", extended, red, green, blue)
+        }
+    }
+}
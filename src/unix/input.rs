@@ -0,0 +1,799 @@
+use core::drawing::point_2d::Point2d;
+use core::drawing::size_2d::Size2d;
+use core::events::event::{
+    Event, KeyboardEvent, KeyboardEventType, MouseEvent, MouseEventType, ScrollAxis, WindowEvent,
+    WindowEventType,
+};
+use core::input::key::Key;
+use std::mem::take;
+use std::str::from_utf8;
+
+/// Sequences longer than this without a final byte are considered garbage.
+const MAX_SEQUENCE_LENGTH: usize = 32;
+
+/// The xterm mode that reports the mouse in pixels with the SGR format.
+const SGR_PIXEL_MOUSE_MODE: u32 = 1016;
+
+/// Marks the end of a bracketed paste, started by `ESC [ 200 ~`.
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+#[derive(Debug, Copy, Clone, Default)]
+struct Modifiers {
+    shift: bool,
+    alt: bool,
+    control: bool,
+}
+
+impl Modifiers {
+    /// Decodes the xterm modifier parameter (1 + shift | alt << 1 | control << 2).
+    fn from_parameter(parameter: u32) -> Modifiers {
+        let bits = parameter.saturating_sub(1);
+
+        Modifiers {
+            shift: bits & 1 != 0,
+            alt: bits & 2 != 0,
+            control: bits & 4 != 0,
+        }
+    }
+}
+
+/// Translates the bytes read from the terminal into keyboard and mouse events.
+///
+/// Terminals only report key presses, so every key produces a `KeyDown`
+/// event immediately followed by a `KeyUp` event. The mouse is expected to
+/// be reported with the xterm SGR extended mode (1006), or with the SGR pixel
+/// mode (1016) once it is enabled with `set_pixel_mouse`. The `key_code` of
+/// the keyboard events holds the last byte of the sequence that produced the
+/// key. The typed characters are also reported as text events, and a
+/// bracketed paste (2004) as a single paste event.
+#[derive(Debug)]
+pub struct InputParser {
+    pending: Vec<u8>,
+    left_button: bool,
+    middle_button: bool,
+    right_button: bool,
+    extra_button_1: bool,
+    extra_button_2: bool,
+    char_size: Option<Size2d>,
+    pixel_position: Option<Point2d>,
+    pixel_mouse_supported: Option<bool>,
+    paste: Option<Vec<u8>>,
+}
+
+impl InputParser {
+    pub fn new() -> InputParser {
+        InputParser {
+            pending: Vec::new(),
+            left_button: false,
+            middle_button: false,
+            right_button: false,
+            extra_button_1: false,
+            extra_button_2: false,
+            char_size: None,
+            pixel_position: None,
+            pixel_mouse_supported: None,
+            paste: None,
+        }
+    }
+
+    /// Sets the character size in pixels used to convert the mouse reports
+    /// of the SGR pixel mode into cells, or none when the mode is disabled.
+    pub fn set_pixel_mouse(&mut self, char_size: Option<Size2d>) {
+        self.char_size = char_size;
+    }
+
+    /// Gets the position in pixels of the last mouse report, when the SGR
+    /// pixel mode is enabled.
+    #[inline]
+    pub fn get_pixel_position(&self) -> Option<Point2d> {
+        self.pixel_position
+    }
+
+    /// Takes the answer of the terminal to the SGR pixel mode query, if it
+    /// arrived since the last call.
+    pub fn take_pixel_mouse_supported(&mut self) -> Option<bool> {
+        self.pixel_mouse_supported.take()
+    }
+
+    /// Parses the given bytes, appending the resulting events. Incomplete
+    /// sequences at the end of the input, including a lone escape byte, are
+    /// kept until the next call or a `flush`.
+    pub fn parse(&mut self, bytes: &[u8], events: &mut Vec<Event>) {
+        let mut input = take(&mut self.pending);
+        input.extend_from_slice(bytes);
+
+        let mut index = 0;
+
+        while index < input.len() {
+            match self.parse_next(&input[index..], events) {
+                Some(consumed) => index += consumed,
+                None => {
+                    self.pending = input[index..].to_vec();
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Checks if the end of the input is an incomplete sequence, waiting for
+    /// more bytes. A bracketed paste that hasn't ended doesn't count, as it
+    /// can't time out.
+    #[inline]
+    pub fn has_incomplete_sequence(&self) -> bool {
+        self.paste.is_none() && !self.pending.is_empty()
+    }
+
+    /// Parses the kept bytes as if no more bytes were coming, once the rest
+    /// of a sequence didn't arrive in time: a lone escape byte is the escape
+    /// key, and an unfinished `ESC [` or `ESC O` is alt with `[` or `O`,
+    /// followed by the bytes after it.
+    pub fn flush(&mut self, events: &mut Vec<Event>) {
+        let input = take(&mut self.pending);
+        let mut index = 0;
+
+        while index < input.len() {
+            if self.paste.is_some() {
+                self.pending = input[index..].to_vec();
+                break;
+            }
+
+            index += match self.parse_next(&input[index..], events) {
+                Some(consumed) => consumed,
+                None => parse_timed_out(&input[index..], events),
+            };
+        }
+    }
+
+    /// Parses the next key or mouse sequence, returning the amount of bytes
+    /// consumed or `None` if more bytes are required.
+    fn parse_next(&mut self, input: &[u8], events: &mut Vec<Event>) -> Option<usize> {
+        if self.paste.is_some() {
+            return self.parse_paste(input, events);
+        }
+
+        if input[0] != 0x1b {
+            return parse_character(input, Modifiers::default(), events);
+        }
+
+        // the rest of the sequence may come with the next read.
+        if input.len() == 1 {
+            return None;
+        }
+
+        match input[1] {
+            b'[' => self.parse_csi(input, events),
+            b'O' => parse_ss3(input, events),
+            0x1b => {
+                push_key(events, Key::Escape, '\u{1b}', 0x1b, Modifiers::default());
+                Some(1)
+            }
+            _ => {
+                let modifiers = Modifiers {
+                    alt: true,
+                    ..Modifiers::default()
+                };
+                parse_character(&input[1..], modifiers, events).map(|consumed| consumed + 1)
+            }
+        }
+    }
+
+    /// Parses a control sequence (`ESC [ parameters final`).
+    fn parse_csi(&mut self, input: &[u8], events: &mut Vec<Event>) -> Option<usize> {
+        let final_index = match input
+            .iter()
+            .skip(2)
+            .position(|byte| (0x40..=0x7e).contains(byte))
+        {
+            Some(position) => position + 2,
+            None if input.len() > MAX_SEQUENCE_LENGTH => return Some(input.len()),
+            None => return None,
+        };
+
+        let final_byte = input[final_index];
+        let parameters = &input[2..final_index];
+
+        if parameters.first() == Some(&b'<') {
+            self.parse_sgr_mouse(&parameters[1..], final_byte, events);
+            return Some(final_index + 1);
+        }
+
+        // mode reports (`ESC [ ? mode ; value $ y`), the answers to DECRQM.
+        if parameters.first() == Some(&b'?') && final_byte == b'y' {
+            let parameters = parse_parameters(parameters);
+
+            if parameters.first() == Some(&SGR_PIXEL_MOUSE_MODE) {
+                // 0 is an unknown mode and 4 a permanently disabled one.
+                let value = parameters.get(1).cloned().unwrap_or(0);
+                self.pixel_mouse_supported = Some(value != 0 && value != 4);
+            }
+
+            return Some(final_index + 1);
+        }
+
+        // xterm focus reports (`ESC [ I` and `ESC [ O`).
+        if parameters.is_empty() && (final_byte == b'I' || final_byte == b'O') {
+            push_focus(events, final_byte == b'I');
+            return Some(final_index + 1);
+        }
+
+        let parameters = parse_parameters(parameters);
+        let modifiers = Modifiers::from_parameter(parameters.get(1).cloned().unwrap_or(1));
+
+        let key = match final_byte {
+            b'A' => Key::Up,
+            b'B' => Key::Down,
+            b'C' => Key::Right,
+            b'D' => Key::Left,
+            b'E' => Key::Clear,
+            b'F' => Key::End,
+            b'H' => Key::Home,
+            b'P' => Key::F1,
+            b'Q' => Key::F2,
+            b'R' => Key::F3,
+            b'S' => Key::F4,
+            b'Z' => {
+                let modifiers = Modifiers {
+                    shift: true,
+                    ..modifiers
+                };
+                push_key(events, Key::Tab, '\t', final_byte, modifiers);
+                return Some(final_index + 1);
+            }
+            b'~' if parameters.first() == Some(&200) => {
+                self.paste = Some(Vec::new());
+                return Some(final_index + 1);
+            }
+            b'~' => get_tilde_key(parameters.first().cloned().unwrap_or(0)),
+            _ => Key::None,
+        };
+
+        if key != Key::None {
+            push_key(events, key, '\0', final_byte, modifiers);
+        }
+
+        Some(final_index + 1)
+    }
+
+    /// Collects the pasted bytes until the end of the bracketed paste, and
+    /// reports them as a single paste event.
+    fn parse_paste(&mut self, input: &[u8], events: &mut Vec<Event>) -> Option<usize> {
+        let mut paste = self.paste.take().unwrap_or_default();
+
+        let end = input
+            .windows(PASTE_END.len())
+            .position(|window| window == PASTE_END);
+
+        if let Some(end) = end {
+            paste.extend_from_slice(&input[..end]);
+            let text = String::from_utf8_lossy(&paste).replace("\r\n", "\n");
+            events.push(Event::Paste(text.replace('\r', "\n")));
+            return Some(end + PASTE_END.len());
+        }
+
+        // the end of the input may be the start of the end marker.
+        let kept = (1..PASTE_END.len())
+            .rev()
+            .find(|length| input.ends_with(&PASTE_END[..*length]))
+            .unwrap_or(0);
+
+        let consumed = input.len() - kept;
+        paste.extend_from_slice(&input[..consumed]);
+        self.paste = Some(paste);
+
+        if consumed == 0 {
+            None
+        } else {
+            Some(consumed)
+        }
+    }
+
+    /// Parses a SGR mouse report (`ESC [ < button ; x ; y M` or `m` on release).
+    fn parse_sgr_mouse(&mut self, parameters: &[u8], final_byte: u8, events: &mut Vec<Event>) {
+        let parameters = parse_parameters(parameters);
+
+        if parameters.len() < 3 {
+            return;
+        }
+
+        let code = parameters[0];
+        let position = Point2d::new(
+            parameters[1].saturating_sub(1) as i32,
+            parameters[2].saturating_sub(1) as i32,
+        );
+
+        let position = match self.char_size {
+            Some(char_size) if char_size.width != 0 && char_size.height != 0 => {
+                self.pixel_position = Some(position);
+                Point2d::new(
+                    position.x / char_size.width as i32,
+                    position.y / char_size.height as i32,
+                )
+            }
+            _ => position,
+        };
+        let pressed = final_byte == b'M';
+        let motion = code & 32 != 0;
+
+        let event_type = if code & 64 != 0 && code & 128 == 0 {
+            // wheel notches are reported as presses of buttons 4 to 7 (up,
+            // down, left and right), each one scrolls a line.
+            let lines = if code & 1 == 0 { 1 } else { -1 };
+
+            if code & 2 == 0 {
+                MouseEventType::Scroll {
+                    axis: ScrollAxis::Vertical,
+                    lines,
+                }
+            } else {
+                MouseEventType::Scroll {
+                    axis: ScrollAxis::Horizontal,
+                    lines: -lines,
+                }
+            }
+        } else if motion {
+            MouseEventType::MouseMove
+        } else {
+            match (code & 128 != 0, code & 3) {
+                (false, 0) => self.left_button = pressed,
+                (false, 1) => self.middle_button = pressed,
+                (false, 2) => self.right_button = pressed,
+                (true, 0) => self.extra_button_1 = pressed,
+                (true, 1) => self.extra_button_2 = pressed,
+                _ => (),
+            }
+
+            MouseEventType::Click
+        };
+
+        events.push(Event::Mouse(MouseEvent {
+            event_type,
+            left_button: self.left_button,
+            middle_button: self.middle_button,
+            right_button: self.right_button,
+            extra_button_1: self.extra_button_1,
+            extra_button_2: self.extra_button_2,
+            extra_button_3: false,
+            extra_button_4: false,
+            position,
+        }));
+    }
+}
+
+impl Default for InputParser {
+    fn default() -> InputParser {
+        InputParser::new()
+    }
+}
+
+/// Parses the start of an incomplete sequence that timed out, returning the
+/// amount of bytes consumed. Incomplete characters are dropped.
+fn parse_timed_out(input: &[u8], events: &mut Vec<Event>) -> usize {
+    if input[0] != 0x1b {
+        return 1;
+    }
+
+    if input.len() == 1 {
+        push_key(events, Key::Escape, '\u{1b}', 0x1b, Modifiers::default());
+        return 1;
+    }
+
+    let modifiers = Modifiers {
+        alt: true,
+        ..Modifiers::default()
+    };
+
+    parse_character(&input[1..2], modifiers, events).map_or(2, |consumed| consumed + 1)
+}
+
+/// Parses a single shift sequence (`ESC O final`), used by the function and
+/// cursor keys in application mode.
+fn parse_ss3(input: &[u8], events: &mut Vec<Event>) -> Option<usize> {
+    if input.len() < 3 {
+        return None;
+    }
+
+    let key = match input[2] {
+        b'A' => Key::Up,
+        b'B' => Key::Down,
+        b'C' => Key::Right,
+        b'D' => Key::Left,
+        b'F' => Key::End,
+        b'H' => Key::Home,
+        b'P' => Key::F1,
+        b'Q' => Key::F2,
+        b'R' => Key::F3,
+        b'S' => Key::F4,
+        _ => Key::None,
+    };
+
+    if key != Key::None {
+        push_key(events, key, '\0', input[2], Modifiers::default());
+    }
+
+    Some(3)
+}
+
+/// Parses a single UTF-8 encoded character or control byte.
+fn parse_character(input: &[u8], modifiers: Modifiers, events: &mut Vec<Event>) -> Option<usize> {
+    let byte = input[0];
+
+    let (key, character, modifiers) = match byte {
+        0x0d | 0x0a => (Key::Return, '\r', modifiers),
+        0x09 => (Key::Tab, '\t', modifiers),
+        0x08 | 0x7f => (Key::Back, '\u{8}', modifiers),
+        0x00 => (Key::Space, '\0', with_control(modifiers)),
+        0x01..=0x1a => (
+            get_letter_key(byte - 1),
+            byte as char,
+            with_control(modifiers),
+        ),
+        0x1c => (Key::Oem5, byte as char, with_control(modifiers)),
+        0x1d => (Key::Oem6, byte as char, with_control(modifiers)),
+        0x1e => (Key::Key6, byte as char, with_control(modifiers)),
+        0x1f => (Key::Minus, byte as char, with_control(modifiers)),
+        0x20..=0x7e => {
+            let character = byte as char;
+            let (key, shift) = get_key_from_char(character);
+            let modifiers = Modifiers {
+                shift: modifiers.shift || shift,
+                ..modifiers
+            };
+            (key, character, modifiers)
+        }
+        _ => {
+            let length = match byte {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => return Some(1),
+            };
+
+            if input.len() < length {
+                return None;
+            }
+
+            let character = match from_utf8(&input[..length]) {
+                Ok(text) => text.chars().next().unwrap_or('\0'),
+                Err(_) => return Some(1),
+            };
+
+            push_key(events, Key::None, character, byte, modifiers);
+            push_text(events, character, modifiers);
+            return Some(length);
+        }
+    };
+
+    push_key(events, key, character, byte, modifiers);
+    push_text(events, character, modifiers);
+    Some(1)
+}
+
+/// Reports a typed character as text, unless it is a control character or
+/// part of a control or alt combination.
+fn push_text(events: &mut Vec<Event>, character: char, modifiers: Modifiers) {
+    if !character.is_control() && !modifiers.control && !modifiers.alt {
+        events.push(Event::Text(character.to_string()));
+    }
+}
+
+/// Pushes a key down event followed by its key up event.
+fn push_key(events: &mut Vec<Event>, key: Key, character: char, code: u8, modifiers: Modifiers) {
+    for event_type in [KeyboardEventType::KeyDown, KeyboardEventType::KeyUp].iter() {
+        events.push(Event::Keyboard(KeyboardEvent {
+            event_type: *event_type,
+            key,
+            key_code: code as u16,
+            character,
+            left_control: modifiers.control,
+            left_shift: modifiers.shift,
+            left_menu: modifiers.alt,
+            right_control: false,
+            right_shift: false,
+            right_menu: false,
+        }));
+    }
+}
+
+/// Pushes a focus event. The terminal doesn't report the window position
+/// nor its size, so the application fills the size in.
+fn push_focus(events: &mut Vec<Event>, focused: bool) {
+    events.push(Event::Window(WindowEvent {
+        event_type: if focused {
+            WindowEventType::WindowFocus
+        } else {
+            WindowEventType::WindowLostFocus
+        },
+        position: Point2d::empty(),
+        size: Size2d::empty(),
+    }));
+}
+
+#[inline]
+fn with_control(modifiers: Modifiers) -> Modifiers {
+    Modifiers {
+        control: true,
+        ..modifiers
+    }
+}
+
+/// Parses a list of numeric parameters separated by semicolons.
+fn parse_parameters(parameters: &[u8]) -> Vec<u32> {
+    parameters
+        .split(|byte| *byte == b';')
+        .map(|parameter| {
+            parameter
+                .iter()
+                .filter(|byte| byte.is_ascii_digit())
+                .fold(0u32, |value, byte| {
+                    value
+                        .saturating_mul(10)
+                        .saturating_add((byte - b'0') as u32)
+                })
+        })
+        .collect()
+}
+
+/// Gets the key of a `ESC [ number ~` sequence.
+fn get_tilde_key(number: u32) -> Key {
+    match number {
+        1 | 7 => Key::Home,
+        2 => Key::Insert,
+        3 => Key::Delete,
+        4 | 8 => Key::End,
+        5 => Key::Prior,
+        6 => Key::Next,
+        11 => Key::F1,
+        12 => Key::F2,
+        13 => Key::F3,
+        14 => Key::F4,
+        15 => Key::F5,
+        17 => Key::F6,
+        18 => Key::F7,
+        19 => Key::F8,
+        20 => Key::F9,
+        21 => Key::F10,
+        23 => Key::F11,
+        24 => Key::F12,
+        25 => Key::F13,
+        26 => Key::F14,
+        28 => Key::F15,
+        29 => Key::F16,
+        31 => Key::F17,
+        32 => Key::F18,
+        33 => Key::F19,
+        34 => Key::F20,
+        _ => Key::None,
+    }
+}
+
+/// Gets the letter key from its alphabet index (0 for A).
+fn get_letter_key(index: u8) -> Key {
+    match index {
+        0 => Key::A,
+        1 => Key::B,
+        2 => Key::C,
+        3 => Key::D,
+        4 => Key::E,
+        5 => Key::F,
+        6 => Key::G,
+        7 => Key::H,
+        8 => Key::I,
+        9 => Key::J,
+        10 => Key::K,
+        11 => Key::L,
+        12 => Key::M,
+        13 => Key::N,
+        14 => Key::O,
+        15 => Key::P,
+        16 => Key::Q,
+        17 => Key::R,
+        18 => Key::S,
+        19 => Key::T,
+        20 => Key::U,
+        21 => Key::V,
+        22 => Key::W,
+        23 => Key::X,
+        24 => Key::Y,
+        25 => Key::Z,
+        _ => Key::None,
+    }
+}
+
+/// Gets the key that produces a printable ascii character on an US keyboard
+/// layout, and whether shift has to be pressed to produce it.
+fn get_key_from_char(character: char) -> (Key, bool) {
+    match character {
+        'a'..='z' => (get_letter_key(character as u8 - b'a'), false),
+        'A'..='Z' => (get_letter_key(character as u8 - b'A'), true),
+        ' ' => (Key::Space, false),
+        '0' | ')' => (Key::Key0, character != '0'),
+        '1' | '!' => (Key::Key1, character != '1'),
+        '2' | '@' => (Key::Key2, character != '2'),
+        '3' | '#' => (Key::Key3, character != '3'),
+        '4' | '$' => (Key::Key4, character != '4'),
+        '5' | '%' => (Key::Key5, character != '5'),
+        '6' | '^' => (Key::Key6, character != '6'),
+        '7' | '&' => (Key::Key7, character != '7'),
+        '8' | '*' => (Key::Key8, character != '8'),
+        '9' | '(' => (Key::Key9, character != '9'),
+        ';' | ':' => (Key::Oem1, character != ';'),
+        '=' | '+' => (Key::Plus, character != '='),
+        ',' | '<' => (Key::Comma, character != ','),
+        '-' | '_' => (Key::Minus, character != '-'),
+        '.' | '>' => (Key::Period, character != '.'),
+        '/' | '?' => (Key::Oem2, character != '/'),
+        '`' | '~' => (Key::Oem3, character != '`'),
+        '[' | '{' => (Key::Oem4, character != '['),
+        '\\' | '|' => (Key::Oem5, character != '\\'),
+        ']' | '}' => (Key::Oem6, character != ']'),
+        '\'' | '"' => (Key::Oem7, character != '\''),
+        _ => (Key::None, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::drawing::point_2d::Point2d;
+    use core::drawing::size_2d::Size2d;
+    use core::events::event::{Event, KeyboardEventType, MouseEventType, ScrollAxis};
+    use core::input::key::Key;
+    use unix::input::InputParser;
+
+    /// Gets the pressed keys with their alt modifier.
+    fn get_keys(events: &[Event]) -> Vec<(Key, bool)> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Keyboard(keyboard) if keyboard.event_type == KeyboardEventType::KeyDown => {
+                    Some((keyboard.key, keyboard.left_menu))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn parse_chunks(chunks: &[&[u8]]) -> (InputParser, Vec<Event>) {
+        let mut parser = InputParser::new();
+        let mut events = Vec::new();
+
+        for chunk in chunks {
+            parser.parse(chunk, &mut events);
+        }
+
+        (parser, events)
+    }
+
+    #[test]
+    fn parses_cursor_keys() {
+        let (parser, events) = parse_chunks(&[b"\x1b[A\x1bOB\x1b[1;3C"]);
+        assert_eq!(
+            get_keys(&events),
+            vec![(Key::Up, false), (Key::Down, false), (Key::Right, true)]
+        );
+        assert!(!parser.has_incomplete_sequence());
+    }
+
+    #[test]
+    fn escape_split_across_reads_is_one_sequence() {
+        let (parser, events) = parse_chunks(&[b"a\x1b"]);
+        assert_eq!(get_keys(&events), vec![(Key::A, false)]);
+        assert!(parser.has_incomplete_sequence());
+
+        let (_, events) = parse_chunks(&[b"a\x1b", b"[A"]);
+        assert_eq!(get_keys(&events), vec![(Key::A, false), (Key::Up, false)]);
+
+        let (_, events) = parse_chunks(&[b"\x1b[", b"1;5", b"D"]);
+        assert_eq!(get_keys(&events), vec![(Key::Left, false)]);
+    }
+
+    #[test]
+    fn flush_reports_a_lone_escape() {
+        let (mut parser, mut events) = parse_chunks(&[b"\x1b"]);
+        assert!(events.is_empty());
+
+        parser.flush(&mut events);
+        assert_eq!(get_keys(&events), vec![(Key::Escape, false)]);
+        assert!(!parser.has_incomplete_sequence());
+    }
+
+    #[test]
+    fn flush_reports_an_unfinished_csi_as_alt() {
+        let (mut parser, mut events) = parse_chunks(&[b"\x1b["]);
+        assert!(events.is_empty());
+
+        parser.flush(&mut events);
+        assert_eq!(get_keys(&events), vec![(Key::Oem4, true)]);
+
+        // the next key isn't swallowed by the old prefix.
+        events.clear();
+        parser.parse(b"x", &mut events);
+        assert_eq!(get_keys(&events), vec![(Key::X, false)]);
+    }
+
+    #[test]
+    fn parses_sgr_mouse_reports() {
+        let (_, events) = parse_chunks(&[b"\x1b[<0;5;", b"3M"]);
+
+        match events.as_slice() {
+            [Event::Mouse(mouse)] => {
+                assert_eq!(mouse.event_type, MouseEventType::Click);
+                assert!(mouse.left_button);
+                assert_eq!(mouse.position, Point2d::new(4, 2));
+            }
+            events => panic!("unexpected events {:?}", events),
+        }
+    }
+
+    #[test]
+    fn wheel_notches_scroll_a_line_at_the_cursor() {
+        let (_, events) = parse_chunks(&[b"\x1b[<64;3;2M\x1b[<65;3;2M\x1b[<66;1;1M\x1b[<67;1;1M"]);
+        let scrolls: Vec<_> = events
+            .iter()
+            .map(|event| match event {
+                Event::Mouse(mouse) => (mouse.event_type, mouse.position),
+                event => panic!("unexpected event {:?}", event),
+            })
+            .collect();
+
+        let scroll = |axis, lines| MouseEventType::Scroll { axis, lines };
+        assert_eq!(
+            scrolls,
+            vec![
+                (scroll(ScrollAxis::Vertical, 1), Point2d::new(2, 1)),
+                (scroll(ScrollAxis::Vertical, -1), Point2d::new(2, 1)),
+                (scroll(ScrollAxis::Horizontal, -1), Point2d::new(0, 0)),
+                (scroll(ScrollAxis::Horizontal, 1), Point2d::new(0, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn pixel_reports_are_converted_to_cells() {
+        let mut parser = InputParser::new();
+        let mut events = Vec::new();
+        parser.set_pixel_mouse(Some(Size2d::new(8, 16)));
+        parser.parse(b"\x1b[<35;21;43M", &mut events);
+
+        match events.as_slice() {
+            [Event::Mouse(mouse)] => assert_eq!(mouse.position, Point2d::new(2, 2)),
+            events => panic!("unexpected events {:?}", events),
+        }
+        assert_eq!(parser.get_pixel_position(), Some(Point2d::new(20, 42)));
+
+        // a zero char size keeps the reports in cells.
+        events.clear();
+        parser.set_pixel_mouse(Some(Size2d::new(0, 16)));
+        parser.parse(b"\x1b[<35;21;43M", &mut events);
+
+        match events.as_slice() {
+            [Event::Mouse(mouse)] => assert_eq!(mouse.position, Point2d::new(20, 42)),
+            events => panic!("unexpected events {:?}", events),
+        }
+    }
+
+    #[test]
+    fn parses_a_split_bracketed_paste() {
+        let (parser, events) = parse_chunks(&[b"\x1b[200~one\r\ntw", b"o\x1b[20", b"1~"]);
+
+        match events.as_slice() {
+            [Event::Paste(text)] => assert_eq!(text, "one\ntwo"),
+            events => panic!("unexpected events {:?}", events),
+        }
+
+        assert!(!parser.has_incomplete_sequence());
+    }
+
+    #[test]
+    fn reports_typed_text() {
+        let (_, events) = parse_chunks(&["é!".as_bytes()]);
+        let text: String = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(text, "é!");
+    }
+}
@@ -0,0 +1,41 @@
+extern crate libc;
+use core::error::Error;
+use core::Result;
+use std::io::{stdout, Write};
+use std::mem::zeroed;
+use unix::libc::{ioctl, winsize, STDOUT_FILENO, TIOCGWINSZ};
+
+pub mod application;
+pub mod color;
+pub mod input;
+pub mod mouse;
+pub mod signal;
+pub mod terminal;
+pub mod waker;
+pub mod window;
+
+/// Queries the terminal size, both in character cells and in pixels.
+/// Pixel sizes are reported as 0 by terminals that don't know them.
+pub fn get_winsize() -> Result<winsize> {
+    let mut size = unsafe { zeroed::<winsize>() };
+    let success = unsafe { ioctl(STDOUT_FILENO, TIOCGWINSZ, &mut size) };
+
+    if success == -1 {
+        return Err(Error::last_os_error(
+            "Problems trying to obtain the terminal size.",
+        ));
+    }
+
+    Ok(size)
+}
+
+/// Writes an escape sequence to the terminal and flushes it right away.
+pub fn write_sequence(sequence: &str) -> Result<()> {
+    let stdout = stdout();
+    let mut handle = stdout.lock();
+
+    handle.write_all(sequence.as_bytes())?;
+    handle.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use core::drawing::point_2d::Point2d;
+use core::error::Error;
+use core::mouse::Mouse;
+use core::Result;
+use std::cell::Cell;
+use unix::{get_winsize, write_sequence};
+
+/// Terminals only report the mouse through input sequences, so the mouse
+/// keeps the last cell position received by the application, and the pixel
+/// position when the terminal reports it.
+#[derive(Debug)]
+pub struct UnixMouse {
+    position: Cell<Point2d>,
+    pixel_position: Cell<Option<Point2d>>,
+}
+
+impl UnixMouse {
+    pub fn new() -> UnixMouse {
+        UnixMouse {
+            position: Cell::new(Point2d::empty()),
+            pixel_position: Cell::new(None),
+        }
+    }
+
+    /// Updates the last known mouse position, in pixels.
+    pub fn update_pixel_position(&self, position: Point2d) {
+        self.pixel_position.set(Some(position));
+    }
+
+    /// Updates the last known mouse position, in character units.
+    pub fn update_position(&self, position: Point2d) {
+        self.position.set(position);
+    }
+}
+
+impl Default for UnixMouse {
+    fn default() -> UnixMouse {
+        UnixMouse::new()
+    }
+}
+
+impl Mouse for UnixMouse {
+    fn get_absolute_position(&self) -> Result<Point2d> {
+        // the terminal window position is unknown, so the best
+        // approximation is the position inside the window.
+        self.get_client_position()
+    }
+
+    fn get_client_position(&self) -> Result<Point2d> {
+        if let Some(position) = self.pixel_position.get() {
+            return Ok(position);
+        }
+
+        // without pixel reports the position is the corner of the cell.
+        let size = get_winsize()?;
+        let position = self.position.get();
+
+        if size.ws_col == 0 || size.ws_row == 0 {
+            return Ok(Point2d::empty());
+        }
+
+        Ok(Point2d::new(
+            position.x * (size.ws_xpixel / size.ws_col) as i32,
+            position.y * (size.ws_ypixel / size.ws_row) as i32,
+        ))
+    }
+
+    fn set_position(&self, _position: Point2d) -> Result<()> {
+        Err(Error::Unsupported(
+            "Terminals don't allow to move the mouse cursor.",
+        ))
+    }
+
+    fn show_cursor(&self, visible: bool) -> Result<()> {
+        // xterm pointer mode: never hide, or always hide while inside the window.
+        write_sequence(if visible { "\x1b[>0p" } else { "\x1b[>2p" })
+    }
+}
@@ -0,0 +1,188 @@
+use core::error::Error;
+use core::Result;
+use std::mem::zeroed;
+use std::ptr::null_mut;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::Mutex;
+use unix::libc::{
+    c_int, c_void, fcntl, pipe, read, sigaction, sigemptyset, sighandler_t, write, FD_CLOEXEC,
+    F_GETFL, F_SETFD, F_SETFL, O_NONBLOCK, SIGHUP, SIGTERM, SIGWINCH,
+};
+
+static RESIZED: AtomicBool = AtomicBool::new(false);
+static CLOSE_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// The pipe the handlers write to, so a thread waiting on the terminal input
+/// can also wake up on signals (the self pipe trick).
+static WAKE_READ_FD: AtomicI32 = AtomicI32::new(-1);
+static WAKE_WRITE_FD: AtomicI32 = AtomicI32::new(-1);
+
+/// The signals the handlers are installed for.
+const SIGNALS: [c_int; 3] = [SIGWINCH, SIGHUP, SIGTERM];
+
+/// The actions of the signals before the handlers were installed, restored
+/// by `restore_handlers`.
+static PREVIOUS_ACTIONS: Mutex<Vec<(c_int, sigaction)>> = Mutex::new(Vec::new());
+
+/// Installs the handlers that record the terminal resizes (`SIGWINCH`) and
+/// the close requests (`SIGHUP` when the terminal goes away, `SIGTERM`).
+/// Close requests no longer terminate the process, so the application must
+/// quit when it receives the close event, and restore the previous actions
+/// with `restore_handlers` when it is done with the terminal.
+pub fn install_handlers() -> Result<()> {
+    if WAKE_READ_FD.load(Ordering::SeqCst) == -1 {
+        let mut fds = [-1 as c_int; 2];
+
+        if unsafe { pipe(fds.as_mut_ptr()) } == -1 {
+            return Err(Error::last_os_error("Couldn't create the wake up pipe."));
+        }
+
+        for &fd in fds.iter() {
+            unsafe {
+                fcntl(fd, F_SETFL, fcntl(fd, F_GETFL) | O_NONBLOCK);
+                fcntl(fd, F_SETFD, FD_CLOEXEC);
+            }
+        }
+
+        WAKE_READ_FD.store(fds[0], Ordering::SeqCst);
+        WAKE_WRITE_FD.store(fds[1], Ordering::SeqCst);
+    }
+
+    let mut previous_actions = PREVIOUS_ACTIONS
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+
+    // installing the handlers again keeps the actions saved the first time.
+    if !previous_actions.is_empty() {
+        return Ok(());
+    }
+
+    for &signal in SIGNALS.iter() {
+        let mut action = unsafe { zeroed::<sigaction>() };
+        let mut previous_action = unsafe { zeroed::<sigaction>() };
+        action.sa_sigaction = handle_signal as extern "C" fn(c_int) as sighandler_t;
+        action.sa_flags = 0;
+
+        let success = unsafe {
+            sigemptyset(&mut action.sa_mask);
+            sigaction(signal, &action, &mut previous_action)
+        };
+
+        if success == -1 {
+            let error = Error::last_os_error("Couldn't install the signal handlers.");
+            restore_actions(&mut previous_actions);
+            return Err(error);
+        }
+
+        previous_actions.push((signal, previous_action));
+    }
+
+    Ok(())
+}
+
+/// Restores the actions the signals had before `install_handlers`, so the
+/// close requests terminate the process again.
+pub fn restore_handlers() -> Result<()> {
+    let mut previous_actions = PREVIOUS_ACTIONS
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+
+    if restore_actions(&mut previous_actions) {
+        Ok(())
+    } else {
+        Err(Error::last_os_error(
+            "Couldn't restore the signal handlers.",
+        ))
+    }
+}
+
+/// Restores and forgets the saved actions. Returns false when one of them
+/// couldn't be restored.
+fn restore_actions(previous_actions: &mut Vec<(c_int, sigaction)>) -> bool {
+    let mut restored = true;
+
+    for (signal, previous_action) in previous_actions.drain(..) {
+        if unsafe { sigaction(signal, &previous_action, null_mut()) } == -1 {
+            restored = false;
+        }
+    }
+
+    restored
+}
+
+/// Checks if the terminal was resized since the previous call.
+#[inline]
+pub fn take_resized() -> bool {
+    RESIZED.swap(false, Ordering::SeqCst)
+}
+
+/// Checks if the process was asked to close since the previous call.
+#[inline]
+pub fn take_close_requested() -> bool {
+    CLOSE_REQUESTED.swap(false, Ordering::SeqCst)
+}
+
+/// Gets the file descriptor that becomes readable when a signal arrives.
+#[inline]
+pub fn get_wake_fd() -> c_int {
+    WAKE_READ_FD.load(Ordering::SeqCst)
+}
+
+/// Empties the wake up pipe. Must be called before checking the signal
+/// flags, so a signal received in between wakes up the next wait.
+pub fn clear_wake() {
+    let fd = get_wake_fd();
+    let mut bytes = [0u8; 64];
+
+    while fd != -1 && unsafe { read(fd, bytes.as_mut_ptr() as *mut c_void, bytes.len()) } > 0 {}
+}
+
+/// Wakes up the threads waiting on the wake up pipe.
+#[inline]
+pub fn wake() {
+    let fd = WAKE_WRITE_FD.load(Ordering::SeqCst);
+
+    if fd != -1 {
+        // a full pipe already wakes up the waiting threads.
+        let _ = unsafe { write(fd, &1u8 as *const u8 as *const c_void, 1) };
+    }
+}
+
+extern "C" fn handle_signal(signal: c_int) {
+    // only async signal safe operations are allowed here.
+    if signal == SIGWINCH {
+        RESIZED.store(true, Ordering::SeqCst);
+    } else {
+        CLOSE_REQUESTED.store(true, Ordering::SeqCst);
+    }
+
+    wake();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::zeroed;
+    use std::ptr::null;
+    use unix::libc::{c_int, sigaction, sighandler_t, SIGHUP};
+    use unix::signal::{handle_signal, install_handlers, restore_handlers};
+
+    fn get_handler(signal: c_int) -> sighandler_t {
+        let mut action = unsafe { zeroed::<sigaction>() };
+        assert_eq!(unsafe { sigaction(signal, null(), &mut action) }, 0);
+        action.sa_sigaction
+    }
+
+    #[test]
+    fn restore_handlers_brings_back_the_previous_actions() {
+        let handler = handle_signal as extern "C" fn(c_int) as sighandler_t;
+        let previous_handler = get_handler(SIGHUP);
+
+        install_handlers().unwrap();
+        assert_eq!(get_handler(SIGHUP), handler);
+
+        // a second install doesn't save the handlers as the previous actions.
+        install_handlers().unwrap();
+        restore_handlers().unwrap();
+        assert_eq!(get_handler(SIGHUP), previous_handler);
+    }
+}
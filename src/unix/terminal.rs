@@ -0,0 +1,269 @@
+use core::drawing::cell_buffer::CellBuffer;
+use core::drawing::char_width::get_char_width;
+use core::drawing::color::Color;
+use core::drawing::color_depth::ColorDepth;
+use core::drawing::point_2d::Point2d;
+use core::drawing::row_span::RowSpan;
+use core::drawing::size_2d::Size2d;
+use core::drawing::style::Style;
+use core::error::Error;
+use core::terminal::Terminal;
+use core::window::Window;
+use core::Result;
+use std::cell::{Cell as InteriorCell, RefCell};
+use std::fmt::Write;
+use std::mem::zeroed;
+use unix::color::{
+    get_background_parameters, get_color_depth_from_environment, get_foreground_parameters,
+    get_style_parameters,
+};
+use unix::libc::{
+    c_int, cfmakeraw, isatty, tcgetattr, tcsetattr, termios, STDIN_FILENO, STDOUT_FILENO, TCSANOW,
+    VMIN, VTIME,
+};
+use unix::signal::restore_handlers;
+use unix::{get_winsize, write_sequence};
+
+/// Switches to the alternate screen and disables the line auto wrap, so
+/// writing the last column never scrolls the screen.
+const ENTER_SEQUENCE: &str = "\x1b[?1049h\x1b[?7l";
+
+/// Disables the focus reports and the mouse tracking modes, resets the
+/// attributes, restores the auto wrap and the cursor, and leaves the
+/// alternate screen.
+const LEAVE_SEQUENCE: &str =
+    "\x1b[?2004l\x1b[?1004l\x1b[?1016l\x1b[?1006l\x1b[?1003l\x1b[?1002l\x1b[?1000l\x1b[0m\x1b[?7h\x1b[?25h\x1b[?1049l";
+
+pub struct UnixTerminal {
+    pub input_fd: c_int,
+    original_mode: termios,
+    color_depth: ColorDepth,
+    disposed: InteriorCell<bool>,
+    previous_frame: RefCell<Option<CellBuffer>>,
+}
+
+impl UnixTerminal {
+    pub fn create() -> Result<UnixTerminal> {
+        if unsafe { isatty(STDIN_FILENO) } == 0 || unsafe { isatty(STDOUT_FILENO) } == 0 {
+            return Err(Error::ConsoleUnavailable);
+        }
+
+        let mut original_mode = unsafe { zeroed::<termios>() };
+        let success = unsafe { tcgetattr(STDIN_FILENO, &mut original_mode) };
+
+        if success == -1 {
+            return Err(Error::last_os_error("Couldn't retrieve the terminal mode."));
+        }
+
+        // raw mode: no echo, no line buffering, no signal keys and reads
+        // that return immediately even when there is no input available.
+        let mut raw_mode = original_mode;
+        unsafe { cfmakeraw(&mut raw_mode) };
+        raw_mode.c_cc[VMIN] = 0;
+        raw_mode.c_cc[VTIME] = 0;
+
+        let success = unsafe { tcsetattr(STDIN_FILENO, TCSANOW, &raw_mode) };
+
+        if success == -1 {
+            return Err(Error::last_os_error("Couldn't set the terminal raw mode."));
+        }
+
+        let terminal = UnixTerminal {
+            input_fd: STDIN_FILENO,
+            original_mode,
+            color_depth: get_color_depth_from_environment(),
+            disposed: InteriorCell::new(false),
+            previous_frame: RefCell::new(None),
+        };
+
+        write_sequence(ENTER_SEQUENCE)?;
+
+        Ok(terminal)
+    }
+
+    /// Writes the spans that changed since the previous frame, given by the
+    /// dirty regions or found by comparing both frames.
+    fn write_spans(&self, cell_buffer: &CellBuffer, regions: Option<&[RowSpan]>) -> Result<()> {
+        let mut previous_frame = self.previous_frame.borrow_mut();
+
+        // only the cells that changed since the previous frame are sent,
+        // unless there is no previous frame of the same size.
+        let previous = previous_frame
+            .as_ref()
+            .filter(|previous| previous.size.equal_to(cell_buffer.size));
+
+        let spans = match (previous, regions) {
+            // the regions are trusted, as `Terminal::write_regions` requires.
+            (Some(_), Some(regions)) => regions.to_vec(),
+            (Some(previous), None) => cell_buffer.get_changed_spans(previous),
+            (None, _) => (0..cell_buffer.size.height)
+                .map(|row| RowSpan::new(row, 0, cell_buffer.size.width))
+                .collect(),
+        };
+
+        if spans.is_empty() {
+            return Ok(());
+        }
+
+        let mut output = String::new();
+        let mut current_attributes: Option<(Color, Color, Style)> = None;
+
+        for span in spans {
+            let mut cursor_placed = false;
+            let mut continuation_covered = false;
+
+            for x in span.start..span.end {
+                let position = Point2d::new(x as i32, span.row as i32);
+                let cell = cell_buffer.get(position);
+                let is_unchanged = |position: Point2d| {
+                    previous
+                        .is_some_and(|previous| previous.get(position) == cell_buffer.get(position))
+                };
+
+                // the terminal moves the cursor past both cells of a wide
+                // character, and an unchanged wide character skips both.
+                if cell.continuation {
+                    if continuation_covered {
+                        continuation_covered = false;
+                        continue;
+                    }
+
+                    if x > 0 && cell_buffer.get(position.add_x(-1)).is_wide() {
+                        cursor_placed = false;
+                        continue;
+                    }
+                }
+
+                let is_lead = cell.is_wide()
+                    && x + 1 < cell_buffer.size.width
+                    && cell_buffer.get(position.add_x(1)).continuation;
+
+                if is_unchanged(position) && (!is_lead || is_unchanged(position.add_x(1))) {
+                    cursor_placed = false;
+                    continue;
+                }
+
+                if !cursor_placed {
+                    let _ = write!(output, "\x1b[{};{}H", span.row + 1, x + 1);
+                    cursor_placed = true;
+                }
+
+                let attributes = (
+                    cell.foreground.quantize(self.color_depth),
+                    cell.background.quantize(self.color_depth),
+                    cell.style,
+                );
+
+                if current_attributes != Some(attributes) {
+                    // resets the previous style before applying the new one.
+                    let _ = write!(
+                        output,
+                        "\x1b[0;{}{};{}m",
+                        get_style_parameters(attributes.2),
+                        get_foreground_parameters(attributes.0),
+                        get_background_parameters(attributes.1)
+                    );
+                    current_attributes = Some(attributes);
+                }
+
+                // control and zero width characters would move the cursor,
+                // and wide characters without a continuation cell would
+                // overflow into the next cell.
+                if cell.continuation
+                    || cell.character.is_control()
+                    || get_char_width(cell.character) == 0
+                    || (cell.is_wide() && !is_lead)
+                {
+                    output.push(' ');
+                } else {
+                    output.push(cell.character);
+                    output.extend(cell.get_marks());
+                    continuation_covered = is_lead;
+                }
+            }
+        }
+
+        write_sequence(&output)?;
+
+        match *previous_frame {
+            Some(ref mut previous) => previous.copy_from(cell_buffer),
+            None => *previous_frame = Some(cell_buffer.clone()),
+        }
+
+        Ok(())
+    }
+}
+
+impl Terminal for UnixTerminal {
+    fn dispose(&self) -> Result<()> {
+        if self.disposed.replace(true) {
+            return Ok(());
+        }
+
+        // the signals are restored even when the terminal can't be.
+        let handlers_restored = restore_handlers();
+        write_sequence(LEAVE_SEQUENCE)?;
+
+        let success = unsafe { tcsetattr(self.input_fd, TCSANOW, &self.original_mode) };
+
+        if success == -1 {
+            return Err(Error::last_os_error("Couldn't restore the terminal mode."));
+        }
+
+        handlers_restored
+    }
+
+    fn set_cursor_visibility(&self, visible: bool) -> Result<()> {
+        write_sequence(if visible { "\x1b[?25h" } else { "\x1b[?25l" })
+    }
+
+    fn set_cursor(&self, position: Point2d) -> Result<()> {
+        write_sequence(&format!(
+            "\x1b[{};{}H",
+            position.y.max(0) + 1,
+            position.x.max(0) + 1
+        ))
+    }
+
+    fn get_console_size(&self) -> Result<Size2d> {
+        let size = get_winsize()?;
+        Ok(Size2d::new(size.ws_col as usize, size.ws_row as usize))
+    }
+
+    fn get_color_depth(&self) -> Result<ColorDepth> {
+        Ok(self.color_depth)
+    }
+
+    fn get_char_size(&self, window: &dyn Window) -> Result<Size2d> {
+        let console_size = self.get_console_size()?;
+        let client_size = window.get_window_client_size()?;
+
+        if console_size.width == 0 || console_size.height == 0 {
+            return Ok(Size2d::empty());
+        }
+
+        Ok(Size2d::new(
+            client_size.width / console_size.width,
+            client_size.height / console_size.height,
+        ))
+    }
+
+    fn clear(&self) -> Result<()> {
+        *self.previous_frame.borrow_mut() = None;
+        write_sequence("\x1b[0m\x1b[2J")
+    }
+
+    fn write(&self, cell_buffer: &CellBuffer) -> Result<()> {
+        self.write_spans(cell_buffer, None)
+    }
+
+    fn write_regions(&self, cell_buffer: &CellBuffer, regions: &[RowSpan]) -> Result<()> {
+        self.write_spans(cell_buffer, Some(regions))
+    }
+}
+
+impl Drop for UnixTerminal {
+    fn drop(&mut self) {
+        let _ = self.dispose();
+    }
+}
@@ -0,0 +1,13 @@
+use core::events::waker::Waker;
+use unix::signal::wake;
+
+/// Wakes up the application through the same pipe the signal handlers use.
+#[derive(Debug)]
+pub struct UnixWaker;
+
+impl Waker for UnixWaker {
+    #[inline]
+    fn wake(&self) {
+        wake();
+    }
+}
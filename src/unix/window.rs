@@ -0,0 +1,51 @@
+use core::drawing::point_2d::Point2d;
+use core::drawing::size_2d::Size2d;
+use core::window::Window;
+use core::Result;
+use unix::{get_winsize, write_sequence};
+
+/// The terminal emulator window. Terminals only report the size of the text
+/// area, and the window manipulation requests (xterm `CSI t`) are ignored by
+/// the emulators that don't support them.
+#[derive(Debug)]
+pub struct UnixWindow {}
+
+impl UnixWindow {
+    pub fn new() -> UnixWindow {
+        UnixWindow {}
+    }
+}
+
+impl Default for UnixWindow {
+    fn default() -> UnixWindow {
+        UnixWindow::new()
+    }
+}
+
+impl Window for UnixWindow {
+    fn get_window_client_size(&self) -> Result<Size2d> {
+        let size = get_winsize()?;
+        Ok(Size2d::new(
+            size.ws_xpixel as usize,
+            size.ws_ypixel as usize,
+        ))
+    }
+
+    fn get_window_size(&self) -> Result<Size2d> {
+        self.get_window_client_size()
+    }
+
+    fn set_window_size(&self, size: Size2d) -> Result<()> {
+        write_sequence(&format!("\x1b[4;{};{}t", size.height, size.width))
+    }
+
+    fn get_window_position(&self) -> Result<Point2d> {
+        // the window position can only be obtained by parsing a terminal
+        // report, which most emulators refuse to send.
+        Ok(Point2d::empty())
+    }
+
+    fn set_window_position(&self, position: Point2d) -> Result<()> {
+        write_sequence(&format!("\x1b[3;{};{}t", position.x, position.y))
+    }
+}
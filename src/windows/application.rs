@@ -1,7 +1,9 @@
 use core::application::Application;
 use core::drawing::point_2d::Point2d;
+use core::drawing::size_2d::Size2d;
+use core::error::Error;
 use core::events::event::{
-    Event, KeyboardEvent, KeyboardEventType, MouseEvent, MouseEventType, WindowEvent,
+    Event, KeyboardEvent, KeyboardEventType, MouseEvent, MouseEventType, ScrollAxis, WindowEvent,
     WindowEventType,
 };
 use core::events::event_queue::EventQueue;
@@ -13,27 +15,38 @@ use core::terminal::Terminal;
 use core::window::Window;
 use core::Result;
 use std::char::from_u32;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use windows::control::{install_handler, take_close_requested};
 use windows::mouse::WindowsMouse;
 use windows::terminal::WindowsTerminal;
+use windows::waker::WindowsWaker;
+use windows::winapi::shared::minwindef::FALSE;
 use windows::winapi::um::consoleapi::{
     GetNumberOfConsoleInputEvents, ReadConsoleInputW, SetConsoleMode,
 };
+use windows::winapi::um::synchapi::WaitForMultipleObjects;
+use windows::winapi::um::winbase::WAIT_FAILED;
 use windows::winapi::um::wincon::LEFT_ALT_PRESSED;
 use windows::winapi::um::wincon::LEFT_CTRL_PRESSED;
 use windows::winapi::um::wincon::RIGHT_ALT_PRESSED;
 use windows::winapi::um::wincon::RIGHT_CTRL_PRESSED;
 use windows::winapi::um::wincon::{
+    DOUBLE_CLICK, ENABLE_MOUSE_INPUT, ENABLE_WINDOW_INPUT, FOCUS_EVENT,
     FROM_LEFT_1ST_BUTTON_PRESSED, FROM_LEFT_2ND_BUTTON_PRESSED, FROM_LEFT_3RD_BUTTON_PRESSED,
-    FROM_LEFT_4TH_BUTTON_PRESSED, DOUBLE_CLICK, ENABLE_MOUSE_INPUT, ENABLE_WINDOW_INPUT,
-    FOCUS_EVENT, INPUT_RECORD, KEY_EVENT, MOUSE_EVENT, MOUSE_HWHEELED, MOUSE_MOVED, MOUSE_WHEELED,
-    RIGHTMOST_BUTTON_PRESSED,
+    FROM_LEFT_4TH_BUTTON_PRESSED, INPUT_RECORD, KEY_EVENT, MOUSE_EVENT, MOUSE_HWHEELED,
+    MOUSE_MOVED, MOUSE_WHEELED, RIGHTMOST_BUTTON_PRESSED, WINDOW_BUFFER_SIZE_EVENT,
 };
 
 use windows::winapi::um::winuser::GetKeyState;
-use windows::winapi::um::winuser::{VK_LSHIFT, VK_RSHIFT};
+use windows::winapi::um::winuser::{VK_LSHIFT, VK_MENU, VK_RSHIFT, WHEEL_DELTA};
 use windows::window::WindowsWindow;
 use windows::Empty;
 
+/// The longest time `wait_events` sleeps before checking the events that
+/// don't signal the console input handle.
+const WAIT_INTERVAL_MILLISECONDS: u64 = 100;
+
 #[allow(dead_code)]
 pub struct WindowsApplication {
     window: WindowsWindow,
@@ -42,18 +55,41 @@ pub struct WindowsApplication {
     event_queue: EventQueue,
     mouse_state: MouseState,
     keyboard_state: KeyboardState,
+    window_position: Point2d,
+    console_size: Size2d,
+
+    /// The wheel units of each axis that don't make a whole line yet, sent
+    /// by the high resolution wheels.
+    scroll_remainders: [i32; 2],
+    waker: Arc<WindowsWaker>,
+
+    /// The first half of a surrogate pair, waiting for the second half.
+    high_surrogate: Option<u16>,
 }
 
 #[allow(dead_code)]
 impl WindowsApplication {
     pub fn create() -> Result<WindowsApplication> {
+        let window = WindowsWindow::new();
+        let terminal = WindowsTerminal::create()?;
+        let window_position = window.get_window_position()?;
+        let console_size = terminal.get_console_size()?;
+        let waker = Arc::new(WindowsWaker::create()?);
+        let mut event_queue = EventQueue::new();
+        event_queue.set_waker(waker.clone());
+
         let application = WindowsApplication {
-            window: WindowsWindow::new(),
-            terminal: WindowsTerminal::create()?,
+            window,
+            terminal,
             mouse: WindowsMouse::new(),
-            event_queue: EventQueue::new(),
+            event_queue,
             mouse_state: MouseState::new(),
             keyboard_state: KeyboardState::new(),
+            window_position,
+            console_size,
+            scroll_remainders: [0; 2],
+            waker,
+            high_surrogate: None,
         };
 
         let success = unsafe {
@@ -63,27 +99,108 @@ impl WindowsApplication {
             )
         };
 
-        if success == -1 {
-            return Err("Couldn't set the console mode.");
+        if success == 0 {
+            return Err(Error::last_os_error("Couldn't set the console mode."));
         }
 
+        install_handler()?;
+
         Ok(application)
     }
+
+    /// Gets the text typed with a key record. The characters outside of the
+    /// basic plane arrive as two records, one for every surrogate, and the
+    /// alt codes arrive with the release of the alt key.
+    fn get_text(&mut self, input_record: &INPUT_RECORD) -> Option<String> {
+        let key_event = unsafe { input_record.Event.KeyEvent() };
+        let unit = unsafe { *key_event.uChar.UnicodeChar() };
+        let alt_code = key_event.wVirtualKeyCode as i32 == VK_MENU;
+
+        if unit == 0 || (key_event.bKeyDown == 0 && !alt_code) {
+            return None;
+        }
+
+        // alt without control is a shortcut, both together are alt gr.
+        let control_state = key_event.dwControlKeyState;
+        let alt = control_state & (LEFT_ALT_PRESSED | RIGHT_ALT_PRESSED) != 0;
+        let control = control_state & (LEFT_CTRL_PRESSED | RIGHT_CTRL_PRESSED) != 0;
+
+        if alt && !control && !alt_code {
+            return None;
+        }
+
+        let units = match unit {
+            0xd800..=0xdbff => {
+                self.high_surrogate = Some(unit);
+                return None;
+            }
+            0xdc00..=0xdfff => vec![self.high_surrogate.take()?, unit],
+            _ => {
+                self.high_surrogate = None;
+                vec![unit]
+            }
+        };
+
+        let text = String::from_utf16(&units).ok()?;
+
+        if text.chars().any(char::is_control) {
+            return None;
+        }
+
+        // a held key reports the repeats in a single record.
+        Some(text.repeat(key_event.wRepeatCount.max(1) as usize))
+    }
+
+    /// Adds a window event with the last known window position and console size.
+    fn add_window_event(&mut self, event_type: WindowEventType) {
+        self.event_queue.add_event(Event::Window(WindowEvent {
+            event_type,
+            position: self.window_position,
+            size: self.console_size,
+        }));
+    }
+
+    /// Adds a resize event when the console size changed. The buffer size
+    /// records aren't sent when only the visible area of the buffer changes,
+    /// so the size is also checked on every call to `listen_events`.
+    fn check_console_size(&mut self) -> Result<()> {
+        let console_size = self.terminal.get_console_size()?;
+
+        if !console_size.equal_to(self.console_size) {
+            self.console_size = console_size;
+            self.add_window_event(WindowEventType::WindowResize);
+        }
+
+        Ok(())
+    }
+
+    /// Adds a move event when the window position changed, as the console
+    /// doesn't report the window moves.
+    fn check_window_position(&mut self) -> Result<()> {
+        let window_position = self.window.get_window_position()?;
+
+        if !window_position.equal_to(self.window_position) {
+            self.window_position = window_position;
+            self.add_window_event(WindowEventType::WindowMove);
+        }
+
+        Ok(())
+    }
 }
 
 impl Application for WindowsApplication {
     #[inline]
-    fn get_terminal(&self) -> &Terminal {
+    fn get_terminal(&self) -> &dyn Terminal {
         &self.terminal
     }
 
     #[inline]
-    fn get_window(&self) -> &Window {
+    fn get_window(&self) -> &dyn Window {
         &self.window
     }
 
     #[inline]
-    fn get_mouse(&self) -> &Mouse {
+    fn get_mouse(&self) -> &dyn Mouse {
         &self.mouse
     }
 
@@ -108,17 +225,30 @@ impl Application for WindowsApplication {
     }
 
     fn listen_events(&mut self) -> Result<()> {
+        self.keyboard_state.begin_frame();
+        self.mouse_state.begin_frame();
+        self.event_queue.receive_sent_events();
+
         let mut input_records = [INPUT_RECORD::empty(); 128];
         let mut events_read: u32 = 0;
 
+        if take_close_requested() {
+            self.add_window_event(WindowEventType::WindowClose);
+        }
+
+        self.check_window_position()?;
+        self.check_console_size()?;
+
         let success =
             unsafe { GetNumberOfConsoleInputEvents(self.terminal.input_handle, &mut events_read) };
 
-        if success == -1 {
-            return Err("Couldn't determine the amount of unread events");
+        if success == 0 {
+            return Err(Error::last_os_error(
+                "Couldn't determine the amount of unread events.",
+            ));
         }
 
-        if events_read <= 0 {
+        if events_read == 0 {
             return Ok(());
         }
 
@@ -131,39 +261,104 @@ impl Application for WindowsApplication {
             )
         };
 
-        if success == -1 {
-            return Err("Couldn't retrieve the console window events.");
+        if success == 0 {
+            return Err(Error::last_os_error(
+                "Couldn't retrieve the console window events.",
+            ));
         }
 
-        for input_record in input_records.iter() {
-            let event = match input_record.EventType {
+        // only the records that were read hold events.
+        for input_record in input_records[..events_read as usize].iter() {
+            let mut text = None;
+
+            let mut event = match input_record.EventType {
                 KEY_EVENT => {
-                    let event = process_key_events(input_record);
-                    self.event_queue.add_event(event);
-                    event
+                    text = self.get_text(input_record);
+                    process_key_events(input_record)
                 }
                 MOUSE_EVENT => {
-                    let event = process_mouse_events(input_record);
-                    self.event_queue.add_event(event);
-                    event
+                    match process_mouse_events(input_record, &mut self.scroll_remainders) {
+                        Some(event) => event,
+                        None => continue,
+                    }
                 }
                 FOCUS_EVENT => {
-                    let event = process_window_events(&self.window)?;
-                    self.event_queue.add_event(event);
-                    event
+                    let focus_event = unsafe { input_record.Event.FocusEvent() };
+                    self.add_window_event(if focus_event.bSetFocus != 0 {
+                        WindowEventType::WindowFocus
+                    } else {
+                        WindowEventType::WindowLostFocus
+                    });
+                    continue;
+                }
+                WINDOW_BUFFER_SIZE_EVENT => {
+                    self.check_console_size()?;
+                    continue;
                 }
                 _ => continue,
             };
 
             match event {
-                Event::Mouse(mouse) => self.mouse_state.update_from_event(mouse),
+                Event::Mouse(ref mut mouse) => {
+                    // the wheel records report the position in screen
+                    // coordinates, so they get the cell of the last event.
+                    if let MouseEventType::Scroll { .. } = mouse.event_type {
+                        mouse.position = self.mouse_state.position;
+                    }
+
+                    self.mouse_state.update_from_event(mouse)
+                }
                 Event::Keyboard(keyboard) => self.keyboard_state.update_from_event(keyboard),
-                Event::Window(_) => (),
+                _ => (),
+            }
+
+            self.event_queue.add_event(event);
+
+            if let Some(text) = text {
+                self.event_queue.add_event(Event::Text(text));
             }
         }
 
         Ok(())
     }
+
+    /// Waits on the console input handle and the wake up event of the
+    /// senders. The window moves, the console resizes and the close requests
+    /// don't signal the handles, so the wait is split in intervals to keep
+    /// checking them.
+    fn wait_events(&mut self, timeout: Option<Duration>) -> Result<()> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            self.listen_events()?;
+
+            if !self.event_queue.is_empty() {
+                return Ok(());
+            }
+
+            let mut interval = Duration::from_millis(WAIT_INTERVAL_MILLISECONDS);
+
+            if let Some(deadline) = deadline {
+                let now = Instant::now();
+
+                if now >= deadline {
+                    return Ok(());
+                }
+
+                interval = interval.min(deadline - now);
+            }
+
+            let milliseconds = interval.as_nanos().div_ceil(1_000_000) as u32;
+            let handles = [self.terminal.input_handle, self.waker.get_handle()];
+            let result = unsafe {
+                WaitForMultipleObjects(handles.len() as u32, handles.as_ptr(), FALSE, milliseconds)
+            };
+
+            if result == WAIT_FAILED {
+                return Err(Error::last_os_error("Couldn't wait for the console input."));
+            }
+        }
+    }
 }
 
 #[inline]
@@ -171,7 +366,7 @@ fn process_key_events(input_record: &INPUT_RECORD) -> Event {
     let keyboard_event = unsafe { input_record.Event.KeyEvent() };
 
     Event::Keyboard(KeyboardEvent {
-        event_type: if keyboard_event.bKeyDown == -1 {
+        event_type: if keyboard_event.bKeyDown == 0 {
             KeyboardEventType::KeyUp
         } else {
             KeyboardEventType::KeyDown
@@ -188,19 +383,42 @@ fn process_key_events(input_record: &INPUT_RECORD) -> Event {
     })
 }
 
+/// Creates the mouse event of a record. Gets none for the wheel records that
+/// don't scroll a whole line.
 #[inline]
-fn process_mouse_events(input_record: &INPUT_RECORD) -> Event {
+fn process_mouse_events(
+    input_record: &INPUT_RECORD,
+    scroll_remainders: &mut [i32; 2],
+) -> Option<Event> {
     let mouse_event = unsafe { input_record.Event.MouseEvent() };
 
-    Event::Mouse(MouseEvent {
-        event_type: match mouse_event.dwEventFlags {
-            0 => MouseEventType::Click,
-            MOUSE_MOVED => MouseEventType::MouseMove,
-            MOUSE_WHEELED => MouseEventType::HorizontalWheel,
-            MOUSE_HWHEELED => MouseEventType::VerticalWheel,
-            DOUBLE_CLICK => MouseEventType::DoubleClick,
-            _ => MouseEventType::MouseMove,
-        },
+    let event_type = match mouse_event.dwEventFlags {
+        // the mouse state counts the clicks, like on the other backends.
+        0 | DOUBLE_CLICK => MouseEventType::Click,
+        MOUSE_MOVED => MouseEventType::MouseMove,
+        MOUSE_WHEELED | MOUSE_HWHEELED => {
+            let axis = if mouse_event.dwEventFlags == MOUSE_WHEELED {
+                ScrollAxis::Vertical
+            } else {
+                ScrollAxis::Horizontal
+            };
+
+            let lines = get_scroll_lines(
+                &mut scroll_remainders[axis as usize],
+                get_wheel_delta(mouse_event.dwButtonState),
+            );
+
+            if lines == 0 {
+                return None;
+            }
+
+            MouseEventType::Scroll { axis, lines }
+        }
+        _ => MouseEventType::MouseMove,
+    };
+
+    Some(Event::Mouse(MouseEvent {
+        event_type,
         left_button: mouse_event.dwButtonState & FROM_LEFT_1ST_BUTTON_PRESSED != 0,
         middle_button: mouse_event.dwButtonState & FROM_LEFT_2ND_BUTTON_PRESSED != 0,
         right_button: mouse_event.dwButtonState & RIGHTMOST_BUTTON_PRESSED != 0,
@@ -208,20 +426,10 @@ fn process_mouse_events(input_record: &INPUT_RECORD) -> Event {
         extra_button_2: mouse_event.dwButtonState & FROM_LEFT_4TH_BUTTON_PRESSED != 0,
         extra_button_3: false,
         extra_button_4: false,
-        wheel_delta: get_wheel_delta(mouse_event.dwButtonState),
         position: Point2d::new(
-            mouse_event.dwMousePosition.X as usize,
-            mouse_event.dwMousePosition.Y as usize,
+            mouse_event.dwMousePosition.X as i32,
+            mouse_event.dwMousePosition.Y as i32,
         ),
-    })
-}
-
-#[inline]
-fn process_window_events(window: &WindowsWindow) -> Result<Event> {
-    Ok(Event::Window(WindowEvent {
-        event_type: WindowEventType::WindowFocus,
-        position: window.get_window_position()?,
-        size: window.get_window_size()?,
     }))
 }
 
@@ -230,12 +438,20 @@ fn get_wheel_delta(button_state: u32) -> i16 {
     (button_state >> 16) as i16
 }
 
+/// Adds the wheel units to the remainder of an axis and gets the whole lines
+/// they scroll, leaving the rest in the remainder.
+#[inline]
+fn get_scroll_lines(remainder: &mut i32, delta: i16) -> i32 {
+    *remainder += delta as i32;
+
+    let lines = *remainder / WHEEL_DELTA as i32;
+    *remainder -= lines * WHEEL_DELTA as i32;
+    lines
+}
+
 #[inline]
 fn get_char_from_u16(unicode: u16) -> char {
-    match from_u32(unicode as u32) {
-        Some(character) => character,
-        None => ' ',
-    }
+    from_u32(unicode as u32).unwrap_or(' ')
 }
 
 fn get_key(virtual_key_code: u16) -> Key {
@@ -751,3 +967,37 @@ fn get_key(virtual_key_code: u16) -> Key {
         _ => Key::None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use windows::application::{get_scroll_lines, get_wheel_delta};
+
+    #[test]
+    fn wheel_delta_is_the_signed_high_word() {
+        assert_eq!(get_wheel_delta(120 << 16), 120);
+        assert_eq!(get_wheel_delta(((-240i16 as u16) as u32) << 16 | 1), -240);
+    }
+
+    #[test]
+    fn whole_notches_scroll_a_line_each() {
+        let mut remainder = 0;
+
+        assert_eq!(get_scroll_lines(&mut remainder, 120), 1);
+        assert_eq!(get_scroll_lines(&mut remainder, -360), -3);
+        assert_eq!(remainder, 0);
+    }
+
+    #[test]
+    fn high_resolution_deltas_add_up_to_lines() {
+        let mut remainder = 0;
+
+        assert_eq!(get_scroll_lines(&mut remainder, 40), 0);
+        assert_eq!(get_scroll_lines(&mut remainder, 40), 0);
+        assert_eq!(get_scroll_lines(&mut remainder, 60), 1);
+        assert_eq!(remainder, 20);
+
+        assert_eq!(get_scroll_lines(&mut remainder, -20), 0);
+        assert_eq!(get_scroll_lines(&mut remainder, -100), 0);
+        assert_eq!(remainder, -100);
+    }
+}
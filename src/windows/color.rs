@@ -1,4 +1,9 @@
 use core::drawing::color::Color;
+use core::drawing::color_depth::ColorDepth;
+use core::drawing::style::Style;
+use windows::winapi::um::wincon::{
+    COMMON_LVB_REVERSE_VIDEO, COMMON_LVB_UNDERSCORE, FOREGROUND_INTENSITY,
+};
 
 #[inline]
 #[allow(dead_code)]
@@ -27,7 +32,8 @@ pub fn get_color_from_u16(color: u16) -> Color {
 #[inline]
 #[allow(dead_code)]
 pub fn get_u16_from_color(color: Color) -> u16 {
-    match color {
+    // the console attributes can only hold the 16 console colors.
+    match color.quantize(ColorDepth::Ansi16) {
         Color::Black => 0,
         Color::DarkBlue => 1,
         Color::DarkGreen => 2,
@@ -44,5 +50,30 @@ pub fn get_u16_from_color(color: Color) -> u16 {
         Color::Magenta => 13,
         Color::Yellow => 14,
         Color::White => 15,
+        _ => 0,
     }
 }
+
+/// Gets the console attributes of a cell. The console can't display italic,
+/// blinking or struck through text, bold is rendered as an intense foreground
+/// and dim as a non intense one.
+#[inline]
+pub fn get_attributes(foreground: Color, background: Color, style: Style) -> u16 {
+    let mut attributes = get_u16_from_color(foreground) | (get_u16_from_color(background) << 4);
+
+    if style.contains(Style::BOLD) {
+        attributes |= FOREGROUND_INTENSITY;
+    } else if style.contains(Style::DIM) {
+        attributes &= !FOREGROUND_INTENSITY;
+    }
+
+    if style.contains(Style::UNDERLINE) {
+        attributes |= COMMON_LVB_UNDERSCORE;
+    }
+
+    if style.contains(Style::REVERSE) {
+        attributes |= COMMON_LVB_REVERSE_VIDEO;
+    }
+
+    attributes
+}
@@ -0,0 +1,55 @@
+extern crate winapi;
+use core::error::Error;
+use core::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use windows::winapi::shared::minwindef::{BOOL, DWORD, FALSE, TRUE};
+use windows::winapi::um::consoleapi::SetConsoleCtrlHandler;
+use windows::winapi::um::synchapi::Sleep;
+use windows::winapi::um::wincon::{
+    CTRL_BREAK_EVENT, CTRL_CLOSE_EVENT, CTRL_C_EVENT, CTRL_LOGOFF_EVENT, CTRL_SHUTDOWN_EVENT,
+};
+
+/// The time the handler keeps the process alive after a close request. The
+/// system kills the process after 5 seconds anyway.
+const CLOSE_TIMEOUT_MILLISECONDS: DWORD = 4000;
+
+static CLOSE_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Installs the console control handler that records the close requests:
+/// the console window being closed, the user logging off, the system
+/// shutting down and the break keys.
+pub fn install_handler() -> Result<()> {
+    let success = unsafe { SetConsoleCtrlHandler(Some(handle_control), TRUE) };
+
+    if success == 0 {
+        return Err(Error::last_os_error(
+            "Couldn't install the console control handler.",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks if the process was asked to close since the previous call.
+#[inline]
+pub fn take_close_requested() -> bool {
+    CLOSE_REQUESTED.swap(false, Ordering::SeqCst)
+}
+
+unsafe extern "system" fn handle_control(control_type: DWORD) -> BOOL {
+    match control_type {
+        CTRL_CLOSE_EVENT | CTRL_LOGOFF_EVENT | CTRL_SHUTDOWN_EVENT => {
+            CLOSE_REQUESTED.store(true, Ordering::SeqCst);
+
+            // the process is terminated as soon as the handler returns, so it
+            // waits to let the application quit on its own.
+            Sleep(CLOSE_TIMEOUT_MILLISECONDS);
+            TRUE
+        }
+        CTRL_C_EVENT | CTRL_BREAK_EVENT => {
+            CLOSE_REQUESTED.store(true, Ordering::SeqCst);
+            TRUE
+        }
+        _ => FALSE,
+    }
+}
@@ -1,4 +1,7 @@
 extern crate winapi;
+use core::error::Error;
+use core::Result;
+use std::convert::TryFrom;
 use std::mem::zeroed;
 use windows::winapi::shared::windef::{POINT, RECT};
 use windows::winapi::um::wincon::CHAR_INFO;
@@ -9,8 +12,10 @@ use windows::winapi::um::wincon::{
 
 pub mod application;
 pub mod color;
+pub mod control;
 pub mod mouse;
 pub mod terminal;
+pub mod waker;
 pub mod window;
 
 pub trait Empty {
@@ -84,6 +89,22 @@ impl Empty for INPUT_RECORD {
     }
 }
 
+/// Converts a coordinate or a size to the 16 bits range of the console
+/// coordinates.
+#[inline]
+pub fn to_i16<T>(value: T) -> Result<i16>
+where
+    i16: TryFrom<T>,
+{
+    i16::try_from(value).map_err(|_| Error::InvalidSize)
+}
+
+/// Converts a coordinate to the 32 bits range of the window coordinates.
+#[inline]
+pub fn to_i32(value: usize) -> Result<i32> {
+    i32::try_from(value).map_err(|_| Error::InvalidSize)
+}
+
 #[inline]
 pub fn get_wstring(msg: &str) -> Vec<u16> {
     use std::ffi::OsStr;
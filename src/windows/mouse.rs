@@ -1,5 +1,6 @@
 extern crate winapi;
 use core::drawing::point_2d::Point2d;
+use core::error::Error;
 use core::mouse::Mouse;
 use core::Result;
 use std::ptr::null_mut;
@@ -23,39 +24,51 @@ impl WindowsMouse {
     }
 }
 
+impl Default for WindowsMouse {
+    fn default() -> WindowsMouse {
+        WindowsMouse::new()
+    }
+}
+
 impl Mouse for WindowsMouse {
     fn get_absolute_position(&self) -> Result<Point2d> {
         let mut point = POINT::empty();
         let success = unsafe { GetCursorPos(&mut point) };
 
         if success == 0 {
-            return Err("Problems trying to obtain the cursor position.");
+            return Err(Error::last_os_error(
+                "Problems trying to obtain the cursor position.",
+            ));
         }
 
-        Ok(Point2d::new(point.x as usize, point.y as usize))
+        Ok(Point2d::new(point.x, point.y))
     }
 
     fn get_client_position(&self) -> Result<Point2d> {
         let position = self.get_absolute_position()?;
         let mut point = POINT {
-            x: position.x as i32,
-            y: position.y as i32,
+            x: position.x,
+            y: position.y,
         };
 
         let success = unsafe { ScreenToClient(self.window_handle, &mut point) };
 
         if success == 0 {
-            return Err("Problems trying to obtain the client cursor position.");
+            return Err(Error::last_os_error(
+                "Problems trying to obtain the client cursor position.",
+            ));
         }
 
-        Ok(Point2d::new(point.x as usize, point.y as usize))
+        Ok(Point2d::new(point.x, point.y))
     }
 
     fn set_position(&self, position: Point2d) -> Result<()> {
-        let success = unsafe { SetCursorPos(position.x as i32, position.y as i32) };
+        let success = unsafe { SetCursorPos(position.x, position.y) };
 
         if success == 0 {
-            return Err("Problems trying to set the cursor position.");
+            return Err(Error::last_os_error(
+                "Problems trying to set the cursor position.",
+            ));
         }
 
         Ok(())
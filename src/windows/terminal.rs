@@ -1,27 +1,34 @@
 extern crate winapi;
-use core::drawing::cell::Cell;
 use core::drawing::cell_buffer::CellBuffer;
+use core::drawing::char_width::get_char_width;
+use core::drawing::color_depth::ColorDepth;
 use core::drawing::point_2d::Point2d;
+use core::drawing::row_span::RowSpan;
 use core::drawing::size_2d::Size2d;
+use core::error::Error;
 use core::terminal::Terminal;
 use core::window::Window;
 use core::Result;
+use std::cell::RefCell;
+use std::char::REPLACEMENT_CHARACTER;
+use std::fmt::Result as FormatResult;
+use std::fmt::{Debug, Formatter};
 use std::ptr::null_mut;
-use windows::color::get_u16_from_color;
+use windows::color::get_attributes;
 use windows::winapi::ctypes::c_void;
 use windows::winapi::shared::windef::HWND;
 use windows::winapi::um::fileapi::CreateFileW;
-use windows::winapi::um::handleapi::CloseHandle;
+use windows::winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
 use windows::winapi::um::processenv::GetStdHandle;
 use windows::winapi::um::winbase::STD_INPUT_HANDLE;
 use windows::winapi::um::winbase::STD_OUTPUT_HANDLE;
 use windows::winapi::um::wincon::{
     GetConsoleCursorInfo, GetConsoleScreenBufferInfo, GetConsoleWindow, SetConsoleCursorInfo,
-    SetConsoleCursorPosition, WriteConsoleOutputW, CHAR_INFO, CONSOLE_CURSOR_INFO,
-    CONSOLE_SCREEN_BUFFER_INFO, COORD, SMALL_RECT,
+    SetConsoleCursorPosition, WriteConsoleOutputW, CHAR_INFO, COMMON_LVB_LEADING_BYTE,
+    COMMON_LVB_TRAILING_BYTE, CONSOLE_CURSOR_INFO, CONSOLE_SCREEN_BUFFER_INFO, COORD, SMALL_RECT,
 };
 use windows::winapi::um::winnt::HANDLE;
-use windows::{get_wstring, Empty};
+use windows::{get_wstring, to_i16, Empty};
 
 #[derive(Debug)]
 pub struct WindowsTerminal {
@@ -29,6 +36,17 @@ pub struct WindowsTerminal {
     pub output_handle: HANDLE,
     pub input_handle: HANDLE,
     pub window_handle: HWND,
+    previous_frame: RefCell<Option<CellBuffer>>,
+    native_buffer: RefCell<NativeBuffer>,
+}
+
+/// The console representation of the last presented frame.
+struct NativeBuffer(Vec<CHAR_INFO>);
+
+impl Debug for NativeBuffer {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        write!(formatter, "NativeBuffer({} cells)", self.0.len())
+    }
 }
 
 impl WindowsTerminal {
@@ -45,26 +63,27 @@ impl WindowsTerminal {
             )
         };
 
-        if console_handle == null_mut() {
-            return Err("Couldn't open the console output file.");
+        // the console output can't be opened when there is no console.
+        if console_handle.is_null() || console_handle == INVALID_HANDLE_VALUE {
+            return Err(Error::ConsoleUnavailable);
         }
 
         let output_handle = unsafe { GetStdHandle(STD_OUTPUT_HANDLE) };
 
-        if output_handle == null_mut() {
-            return Err("Couldn't retrieve the output handle.");
+        if output_handle.is_null() || output_handle == INVALID_HANDLE_VALUE {
+            return Err(Error::ConsoleUnavailable);
         }
 
         let input_handle = unsafe { GetStdHandle(STD_INPUT_HANDLE) };
 
-        if output_handle == null_mut() {
-            return Err("Couldn't retrieve the input handle.");
+        if input_handle.is_null() || input_handle == INVALID_HANDLE_VALUE {
+            return Err(Error::ConsoleUnavailable);
         }
 
         let window_handle = unsafe { GetConsoleWindow() };
 
-        if window_handle == null_mut() {
-            return Err("Couldn't retrieve the window handle.");
+        if window_handle.is_null() {
+            return Err(Error::ConsoleUnavailable);
         }
 
         Ok(WindowsTerminal {
@@ -72,14 +91,92 @@ impl WindowsTerminal {
             output_handle,
             input_handle,
             window_handle,
+            previous_frame: RefCell::new(None),
+            native_buffer: RefCell::new(NativeBuffer(Vec::new())),
         })
     }
+
+    /// Writes the regions that changed since the previous frame, given by
+    /// the dirty regions or found by comparing both frames.
+    fn write_spans(&self, cell_buffer: &CellBuffer, regions: Option<&[RowSpan]>) -> Result<()> {
+        let width = to_i16(cell_buffer.size.width)?;
+        let height = to_i16(cell_buffer.size.height)?;
+        let mut previous_frame = self.previous_frame.borrow_mut();
+        let native_buffer = &mut self.native_buffer.borrow_mut().0;
+
+        // only the regions that changed since the previous frame are sent,
+        // unless there is no previous frame of the same size.
+        let previous = previous_frame
+            .as_ref()
+            .filter(|previous| previous.size.equal_to(cell_buffer.size));
+
+        let spans = match (previous, regions) {
+            // the regions are trusted, as `Terminal::write_regions` requires.
+            (Some(_), Some(regions)) => regions.to_vec(),
+            (Some(previous), None) => cell_buffer.get_changed_spans(previous),
+            (None, _) => (0..cell_buffer.size.height)
+                .map(|row| RowSpan::new(row, 0, cell_buffer.size.width))
+                .collect(),
+        };
+
+        if previous.is_none() {
+            native_buffer.clear();
+            native_buffer.extend(
+                (0..cell_buffer.size.width * cell_buffer.size.height)
+                    .map(|index| get_char_info(cell_buffer, cell_buffer.coordinates_of(index))),
+            );
+        } else {
+            for span in spans.iter() {
+                for x in span.start..span.end {
+                    let index = x + span.row * cell_buffer.size.width;
+                    native_buffer[index] =
+                        get_char_info(cell_buffer, cell_buffer.coordinates_of(index));
+                }
+            }
+        }
+
+        for mut rect in get_write_regions(&spans)? {
+            let success = unsafe {
+                WriteConsoleOutputW(
+                    self.console_handle,
+                    native_buffer.as_ptr(),
+                    COORD {
+                        X: width,
+                        Y: height,
+                    },
+                    COORD {
+                        X: rect.Left,
+                        Y: rect.Top,
+                    },
+                    &mut rect as *mut SMALL_RECT,
+                )
+            };
+
+            if success == 0 {
+                return Err(Error::last_os_error(
+                    "Couldn't write to the console output.",
+                ));
+            }
+        }
+
+        match *previous_frame {
+            Some(ref mut previous) => previous.copy_from(cell_buffer),
+            None => *previous_frame = Some(cell_buffer.clone()),
+        }
+
+        Ok(())
+    }
 }
 
 #[allow(dead_code)]
 impl Terminal for WindowsTerminal {
     fn dispose(&self) -> Result<()> {
-        unsafe { CloseHandle(self.console_handle) };
+        let success = unsafe { CloseHandle(self.console_handle) };
+
+        if success == 0 {
+            return Err(Error::last_os_error("Couldn't close the console handle."));
+        }
+
         Ok(())
     }
 
@@ -88,15 +185,19 @@ impl Terminal for WindowsTerminal {
         let success = unsafe { GetConsoleCursorInfo(self.output_handle, &mut console_cursor_info) };
 
         if success == 0 {
-            return Err("Problems trying to obtain the console cursor info.");
+            return Err(Error::last_os_error(
+                "Problems trying to obtain the console cursor info.",
+            ));
         }
 
         console_cursor_info.bVisible = if visible { 1 } else { 0 };
 
-        let success = unsafe { SetConsoleCursorInfo(self.output_handle, &mut console_cursor_info) };
+        let success = unsafe { SetConsoleCursorInfo(self.output_handle, &console_cursor_info) };
 
         if success == 0 {
-            return Err("Problems trying to set the console cursor info.");
+            return Err(Error::last_os_error(
+                "Problems trying to set the console cursor info.",
+            ));
         }
 
         Ok(())
@@ -107,14 +208,16 @@ impl Terminal for WindowsTerminal {
             SetConsoleCursorPosition(
                 self.output_handle,
                 COORD {
-                    X: position.x as i16,
-                    Y: position.y as i16,
+                    X: to_i16(position.x)?,
+                    Y: to_i16(position.y)?,
                 },
             )
         };
 
         if success == 0 {
-            return Err("Couldn't set the console cursor position.");
+            return Err(Error::last_os_error(
+                "Couldn't set the console cursor position.",
+            ));
         }
 
         Ok(())
@@ -127,7 +230,9 @@ impl Terminal for WindowsTerminal {
         };
 
         if success == 0 {
-            return Err("Problems trying to obtain the screen buffer info.");
+            return Err(Error::last_os_error(
+                "Problems trying to obtain the screen buffer info.",
+            ));
         }
 
         let window = console_screen_buffer_info.srWindow;
@@ -138,7 +243,11 @@ impl Terminal for WindowsTerminal {
         ))
     }
 
-    fn get_char_size(&self, window: &Window) -> Result<Size2d> {
+    fn get_color_depth(&self) -> Result<ColorDepth> {
+        Ok(ColorDepth::Ansi16)
+    }
+
+    fn get_char_size(&self, window: &dyn Window) -> Result<Size2d> {
         let console_size = self.get_console_size()?;
         let client_size = window.get_window_client_size()?;
 
@@ -153,22 +262,30 @@ impl Terminal for WindowsTerminal {
     }
 
     fn clear(&self) -> Result<()> {
+        *self.previous_frame.borrow_mut() = None;
+
         let size = self.get_console_size()?;
-        let width = size.width as i16;
-        let height = size.height as i16;
+        let width = to_i16(size.width)?;
+        let height = to_i16(size.height)?;
+
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
         let mut char_info = CHAR_INFO::empty();
 
         unsafe {
             *char_info.Char.UnicodeChar_mut() = ' ' as u16;
         }
 
-        let char_info_array = vec![char_info; (width * height) as usize];
+        let char_info_array = vec![char_info; size.width * size.height];
 
+        // the bounds of the rectangle are inclusive.
         let mut rect = SMALL_RECT {
             Left: 0,
             Top: 0,
-            Right: width as i16,
-            Bottom: height as i16,
+            Right: width - 1,
+            Bottom: height - 1,
         };
 
         let success = unsafe {
@@ -176,8 +293,8 @@ impl Terminal for WindowsTerminal {
                 self.console_handle,
                 char_info_array.as_ptr(),
                 COORD {
-                    X: width as i16,
-                    Y: height as i16,
+                    X: width,
+                    Y: height,
                 },
                 COORD::empty(),
                 &mut rect as *mut SMALL_RECT,
@@ -185,53 +302,90 @@ impl Terminal for WindowsTerminal {
         };
 
         if success == 0 {
-            return Err("Couldn't clear console output.");
+            return Err(Error::last_os_error("Couldn't clear console output."));
         }
 
         Ok(())
     }
 
     fn write(&self, cell_buffer: &CellBuffer) -> Result<()> {
-        // TODO: prevent from creating this char once per frame.
-        //       each buffer should store his native representation
-        //       and let the representation be updated when needed.
-        let char_info_array = cell_buffer
-            .iter()
-            .map(|cell: &Cell| {
-                let mut char_info = CHAR_INFO::empty();
-                char_info.Attributes = get_u16_from_color(cell.foreground)
-                    | (get_u16_from_color(cell.background) << 4);
-                unsafe {
-                    *char_info.Char.UnicodeChar_mut() = cell.character as u16;
-                }
-                char_info
-            })
-            .collect::<Vec<CHAR_INFO>>();
+        self.write_spans(cell_buffer, None)
+    }
 
-        let mut rect = SMALL_RECT {
-            Left: 0,
-            Top: 0,
-            Right: cell_buffer.size.width as i16,
-            Bottom: cell_buffer.size.height as i16,
-        };
+    fn write_regions(&self, cell_buffer: &CellBuffer, regions: &[RowSpan]) -> Result<()> {
+        self.write_spans(cell_buffer, Some(regions))
+    }
+}
 
-        let success = unsafe {
-            WriteConsoleOutputW(
-                self.console_handle,
-                char_info_array.as_ptr(),
-                COORD {
-                    X: cell_buffer.size.width as i16,
-                    Y: cell_buffer.size.height as i16,
-                },
-                COORD::empty(),
-                &mut rect as *mut SMALL_RECT,
-            )
-        };
+/// Gets the console representation of a cell. Wide characters are written
+/// as a leading and a trailing cell, each one with a UTF-16 unit of the
+/// character, or the same unit twice when it isn't a surrogate pair. The
+/// console can't combine characters, so combining marks are dropped.
+fn get_char_info(cell_buffer: &CellBuffer, position: Point2d) -> CHAR_INFO {
+    let cell = cell_buffer.get(position);
+    let mut char_info = CHAR_INFO::empty();
+    let mut units = [0u16; 2];
+    char_info.Attributes = get_attributes(cell.foreground, cell.background, cell.style);
+
+    let lead = if position.x > 0 {
+        Some(cell_buffer.get(position.add_x(-1)))
+    } else {
+        None
+    };
+
+    let has_continuation = position.x + 1 < cell_buffer.size.width as i32
+        && cell_buffer.get(position.add_x(1)).continuation;
+
+    let unit = match lead {
+        Some(lead) if cell.continuation && lead.is_wide() => {
+            let encoded = lead.character.encode_utf16(&mut units);
+            char_info.Attributes |= COMMON_LVB_TRAILING_BYTE;
+            encoded[encoded.len() - 1]
+        }
+        _ if cell.continuation => ' ' as u16,
+        _ if cell.is_wide() && has_continuation => {
+            char_info.Attributes |= COMMON_LVB_LEADING_BYTE;
+            cell.character.encode_utf16(&mut units)[0]
+        }
+        _ if cell.is_wide() || cell.character.is_control() => ' ' as u16,
+        _ if get_char_width(cell.character) == 0 => ' ' as u16,
+        // a narrow character outside the basic plane doesn't fit in a cell.
+        _ if cell.character.len_utf16() > 1 => REPLACEMENT_CHARACTER as u16,
+        _ => cell.character as u16,
+    };
+
+    unsafe {
+        *char_info.Char.UnicodeChar_mut() = unit;
+    }
+    char_info
+}
 
-        if success == 0 {
-            return Err("Couldn't write to the console output.");
+/// Merges the changed spans of consecutive rows into the rectangles that
+/// will be written to the console, to reduce the amount of calls.
+fn get_write_regions(spans: &[RowSpan]) -> Result<Vec<SMALL_RECT>> {
+    let mut regions: Vec<SMALL_RECT> = Vec::new();
+
+    for span in spans.iter() {
+        let row = to_i16(span.row)?;
+        let left = to_i16(span.start)?;
+        let right = to_i16(span.end)? - 1;
+
+        if let Some(region) = regions.last_mut() {
+            if region.Bottom + 1 == row {
+                region.Bottom = row;
+                region.Left = region.Left.min(left);
+                region.Right = region.Right.max(right);
+                continue;
+            }
         }
 
-        Ok(())
+        regions.push(SMALL_RECT {
+            Left: left,
+            Top: row,
+            Right: right,
+            Bottom: row,
+        });
     }
+
+    Ok(regions)
 }
@@ -0,0 +1,49 @@
+use core::error::Error;
+use core::events::waker::Waker;
+use core::Result;
+use std::ptr::null_mut;
+use windows::winapi::shared::minwindef::FALSE;
+use windows::winapi::um::handleapi::CloseHandle;
+use windows::winapi::um::synchapi::{CreateEventW, SetEvent};
+use windows::winapi::um::winnt::HANDLE;
+
+/// Wakes up the application with an event object it waits on together with
+/// the console input handle.
+#[derive(Debug)]
+pub struct WindowsWaker {
+    event_handle: HANDLE,
+}
+
+// event objects can be signaled from any thread.
+unsafe impl Send for WindowsWaker {}
+unsafe impl Sync for WindowsWaker {}
+
+impl WindowsWaker {
+    pub fn create() -> Result<WindowsWaker> {
+        let event_handle = unsafe { CreateEventW(null_mut(), FALSE, FALSE, null_mut()) };
+
+        if event_handle.is_null() {
+            return Err(Error::last_os_error("Couldn't create the wake up event."));
+        }
+
+        Ok(WindowsWaker { event_handle })
+    }
+
+    #[inline]
+    pub fn get_handle(&self) -> HANDLE {
+        self.event_handle
+    }
+}
+
+impl Waker for WindowsWaker {
+    #[inline]
+    fn wake(&self) {
+        unsafe { SetEvent(self.event_handle) };
+    }
+}
+
+impl Drop for WindowsWaker {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.event_handle) };
+    }
+}
@@ -1,12 +1,13 @@
 extern crate winapi;
 use core::drawing::point_2d::Point2d;
 use core::drawing::size_2d::Size2d;
+use core::error::Error;
 use core::window::Window;
 use core::Result;
 use windows::winapi::shared::windef::{HWND, RECT};
 use windows::winapi::um::wincon::GetConsoleWindow;
 use windows::winapi::um::winuser::{GetClientRect, GetWindowRect, SetWindowPos};
-use windows::Empty;
+use windows::{to_i32, Empty};
 
 #[derive(Debug)]
 pub struct WindowsWindow {
@@ -21,6 +22,12 @@ impl WindowsWindow {
     }
 }
 
+impl Default for WindowsWindow {
+    fn default() -> WindowsWindow {
+        WindowsWindow::new()
+    }
+}
+
 #[allow(dead_code)]
 impl Window for WindowsWindow {
     fn get_window_size(&self) -> Result<Size2d> {
@@ -28,7 +35,9 @@ impl Window for WindowsWindow {
         let success = unsafe { GetWindowRect(self.window_handle, &mut rect) };
 
         if success == 0 {
-            return Err("Problems trying to obtain the window rect.");
+            return Err(Error::last_os_error(
+                "Problems trying to obtain the window rect.",
+            ));
         }
 
         Ok(Size2d::new(
@@ -42,7 +51,9 @@ impl Window for WindowsWindow {
         let success = unsafe { GetClientRect(self.window_handle, &mut rect) };
 
         if success == 0 {
-            return Err("Problems trying to obtain the client rect.");
+            return Err(Error::last_os_error(
+                "Problems trying to obtain the client rect.",
+            ));
         }
 
         Ok(Size2d::new(
@@ -56,7 +67,9 @@ impl Window for WindowsWindow {
         let success = unsafe { GetWindowRect(self.window_handle, &mut rect) };
 
         if success == 0 {
-            return Err("Problems trying to obtain the window rect.");
+            return Err(Error::last_os_error(
+                "Problems trying to obtain the window rect.",
+            ));
         }
 
         let success = unsafe {
@@ -65,14 +78,16 @@ impl Window for WindowsWindow {
                 0 as HWND,
                 rect.top,
                 rect.left,
-                size.width as i32,
-                size.height as i32,
+                to_i32(size.width)?,
+                to_i32(size.height)?,
                 0x0020 | 0x0040,
             )
         };
 
         if success == 0 {
-            return Err("Problem trying to set the windows size.");
+            return Err(Error::last_os_error(
+                "Problem trying to set the windows size.",
+            ));
         }
 
         Ok(())
@@ -83,10 +98,12 @@ impl Window for WindowsWindow {
         let success = unsafe { GetWindowRect(self.window_handle, &mut rect) };
 
         if success == 0 {
-            return Err("Problems trying to obtain the window rect.");
+            return Err(Error::last_os_error(
+                "Problems trying to obtain the window rect.",
+            ));
         }
 
-        Ok(Point2d::new(rect.left as usize, rect.top as usize))
+        Ok(Point2d::new(rect.left, rect.top))
     }
 
     fn set_window_position(&self, position: Point2d) -> Result<()> {
@@ -94,15 +111,17 @@ impl Window for WindowsWindow {
         let success = unsafe { GetWindowRect(self.window_handle, &mut rect) };
 
         if success == 0 {
-            return Err("Problems trying to obtain the window rect.");
+            return Err(Error::last_os_error(
+                "Problems trying to obtain the window rect.",
+            ));
         }
 
         let success = unsafe {
             SetWindowPos(
                 self.window_handle,
                 0 as HWND,
-                position.x as i32,
-                position.y as i32,
+                position.x,
+                position.y,
                 rect.right - rect.left,
                 rect.bottom - rect.top,
                 0x0020 | 0x0040,
@@ -110,7 +129,9 @@ impl Window for WindowsWindow {
         };
 
         if success == 0 {
-            return Err("Problem trying to set the windows position.");
+            return Err(Error::last_os_error(
+                "Problem trying to set the windows position.",
+            ));
         }
 
         Ok(())
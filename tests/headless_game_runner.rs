@@ -0,0 +1,154 @@
+#![cfg(feature = "headless-backend")]
+
+extern crate rustlike;
+use rustlike::core::application::Application;
+use rustlike::core::drawing::canvas::Canvas;
+use rustlike::core::drawing::cell_buffer::CellBuffer;
+use rustlike::core::drawing::color::Color;
+use rustlike::core::drawing::point_2d::Point2d;
+use rustlike::core::drawing::size_2d::Size2d;
+use rustlike::core::drawing::style::Style;
+use rustlike::core::events::event::{
+    Event, MouseEvent, MouseEventType, WindowEvent, WindowEventType,
+};
+use rustlike::core::game_loop::game::Game;
+use rustlike::core::game_loop::game_runner::GameRunner;
+use rustlike::core::game_loop::loop_mode::LoopMode;
+use rustlike::core::input::mouse_button::MouseButton;
+use rustlike::headless::application::HeadlessApplication;
+use std::thread;
+use std::time::Duration;
+
+/// Writes the typed text and the amount of updates, and quits when `q` is
+/// typed.
+struct Notes {
+    text: String,
+    updates: u32,
+    left_pressed: bool,
+}
+
+impl Notes {
+    fn new() -> Notes {
+        Notes {
+            text: String::new(),
+            updates: 0,
+            left_pressed: false,
+        }
+    }
+}
+
+impl Game for Notes {
+    fn handle_event(&mut self, event: Event, application: &dyn Application) {
+        match event {
+            Event::Text(text) => self.text.push_str(&text),
+            Event::Mouse(_) => {
+                self.left_pressed = application.get_mouse_state().is_pressed(MouseButton::Left)
+            }
+            _ => (),
+        }
+    }
+
+    fn update(&mut self, _dt: Duration, _application: &dyn Application) {
+        self.updates += 1;
+    }
+
+    fn draw(&mut self, buffer: &mut CellBuffer) {
+        let updates = self.updates.to_string();
+        buffer.write_str(
+            &self.text,
+            Point2d::new(0, 0),
+            Color::Grey,
+            Color::Black,
+            Style::NONE,
+        );
+        buffer.write_str(
+            &updates,
+            Point2d::new(0, 1),
+            Color::Grey,
+            Color::Black,
+            Style::NONE,
+        );
+    }
+
+    fn should_quit(&self) -> bool {
+        self.text.ends_with('q')
+    }
+}
+
+fn click(position: Point2d) -> Event {
+    Event::Mouse(MouseEvent {
+        event_type: MouseEventType::Click,
+        left_button: true,
+        middle_button: false,
+        right_button: false,
+        extra_button_1: false,
+        extra_button_2: false,
+        extra_button_3: false,
+        extra_button_4: false,
+        position,
+    })
+}
+
+fn get_row(application: &HeadlessApplication, row: i32) -> String {
+    let last_buffer = application.get_headless_terminal().get_last_buffer();
+    let buffer = last_buffer.as_ref().expect("no frame was presented");
+
+    (0..buffer.size.width as i32)
+        .map(|x| buffer.get(Point2d::new(x, row)).character)
+        .collect()
+}
+
+#[test]
+fn turn_based_game_runs_until_the_window_closes() {
+    let mut application = HeadlessApplication::new(Size2d::new(6, 2));
+    application.push_events(vec![
+        Event::Text("ab".to_string()),
+        click(Point2d::new(1, 1)),
+    ]);
+
+    // the close arrives from another thread, once the runner waits again.
+    let sender = application.get_event_sender();
+    let closer = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        sender
+            .send(Event::Window(WindowEvent {
+                event_type: WindowEventType::WindowClose,
+                position: Point2d::empty(),
+                size: Size2d::new(6, 2),
+            }))
+            .unwrap();
+    });
+
+    let mut notes = Notes::new();
+    let mut runner = GameRunner::new();
+    runner.set_mode(LoopMode::TurnBased);
+    runner.run(&mut application, &mut notes).unwrap();
+    closer.join().unwrap();
+
+    // the first update draws the first frame, the second one follows the
+    // scripted events.
+    assert_eq!(notes.updates, 2);
+    assert!(notes.left_pressed);
+    assert_eq!(get_row(&application, 0), "ab    ");
+    assert_eq!(get_row(&application, 1), "2     ");
+}
+
+#[test]
+fn real_time_game_stops_when_it_quits() {
+    let mut application = HeadlessApplication::new(Size2d::new(4, 2));
+    application.push_event(Event::Text("xq".to_string()));
+
+    let mut notes = Notes::new();
+    let mut runner = GameRunner::new();
+    runner.set_fps_limit(None);
+    runner.run(&mut application, &mut notes).unwrap();
+
+    // the game quits while handling the events of the first frame, before
+    // anything is presented.
+    assert_eq!(notes.text, "xq");
+    assert_eq!(notes.updates, 0);
+    assert!(application
+        .get_headless_terminal()
+        .get_last_buffer()
+        .is_none());
+}